@@ -0,0 +1,225 @@
+//! Module that contains algorithm which chooses between the Bridson and Ebeida algorithms.
+//!
+//! The maximal Ebeida algorithm is preferred, but its running time grows with 2<sup>d</sup> for every sample,
+//! so the faster Bridson algorithm is chosen when the expected amount of samples makes it too slow.
+
+use {Builder, Vector, Float};
+use algorithm::{Creator, Algorithm, bridson, ebeida};
+use obstacle::Obstacle;
+
+use rand::Rng;
+use rand::distributions::{Distribution, Standard};
+
+use sphere::sphere_volume;
+
+/// Amount of work above which the Ebeida algorithm is considered too slow for balanced quality.
+const EBEIDA_BUDGET: f64 = 16_777_216.;
+
+/// Preference between the quality of the distribution and the speed of the generation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Quality {
+    /// Always generates maximal distribution.
+    Maximal,
+    /// Generates maximal distribution unless it's estimated to be too slow.
+    Balanced,
+    /// Always generates distribution as fast as possible.
+    Fast,
+}
+
+impl Default for Quality {
+    fn default() -> Self {
+        Quality::Balanced
+    }
+}
+
+/// Algorithm chosen by the `Auto` algorithm.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Choice {
+    /// The `Bridson` algorithm was chosen.
+    Bridson,
+    /// The `Ebeida` algorithm was chosen.
+    Ebeida,
+}
+
+/// Chooses the algorithm based on the dimension, the estimated amount of work and the quality preference.
+/// The maximal `Ebeida` algorithm is chosen if the expected amount of samples times 2<sup>d</sup> is reasonable
+/// and otherwise the faster `Bridson` algorithm is chosen.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Auto {
+    quality: Quality,
+}
+
+impl Auto {
+    /// New automatic algorithm with the quality preference specified.
+    pub fn new(quality: Quality) -> Self {
+        Auto { quality }
+    }
+
+    /// Returns the quality preference.
+    pub fn quality(&self) -> Quality {
+        self.quality
+    }
+
+    /// Returns the algorithm that would be chosen for the distribution.
+    pub fn choose<F, V>(&self, poisson: &Builder<F, V>) -> Choice
+        where F: Float,
+              V: Vector<F>,
+    {
+        match self.quality {
+            Quality::Maximal => Choice::Ebeida,
+            Quality::Fast => Choice::Bridson,
+            Quality::Balanced => {
+                let dim = V::dimension();
+                // Same as the lower bound of the size hints of both algorithms before generating,
+                // which is the amount of disks needed to cover the whole volume.
+                let expected = (F::cast(1) / sphere_volume(F::cast(2) * poisson.radius, dim as u64))
+                    .to_f64()
+                    .expect("Expected amount of samples should be castable to f64.");
+                if expected * 2f64.powi(dim as i32) <= EBEIDA_BUDGET {
+                    Choice::Ebeida
+                } else {
+                    Choice::Bridson
+                }
+            }
+        }
+    }
+}
+
+impl<F, V> Creator<F, V> for Auto
+    where F: Float,
+          V: Vector<F>,
+          Standard: Distribution<F>,
+          Standard: Distribution<V>,
+{
+    type Algo = Algo<F, V>;
+
+    fn create(poisson: &Builder<F, V>) -> Self::Algo {
+        Auto::default().create_from(poisson)
+    }
+
+    fn create_from(&self, poisson: &Builder<F, V>) -> Self::Algo {
+        match self.choose(poisson) {
+            Choice::Bridson => Algo::Bridson(<bridson::Bridson as Creator<F, V>>::create(poisson)),
+            Choice::Ebeida => Algo::Ebeida(<ebeida::Ebeida as Creator<F, V>>::create(poisson)),
+        }
+    }
+}
+
+/// Implementation for the Auto algorithm
+pub enum Algo<F, V>
+    where F: Float,
+          V: Vector<F>,
+{
+    /// Generates with the Bridson algorithm.
+    Bridson(bridson::Algo<F, V>),
+    /// Generates with the Ebeida algorithm.
+    Ebeida(ebeida::Algo<F, V>),
+}
+
+impl<F, V> Algo<F, V>
+    where F: Float,
+          V: Vector<F>,
+{
+    /// Returns the algorithm that was chosen.
+    pub fn choice(&self) -> Choice {
+        match *self {
+            Algo::Bridson(_) => Choice::Bridson,
+            Algo::Ebeida(_) => Choice::Ebeida,
+        }
+    }
+}
+
+impl<F, V> Algorithm<F, V> for Algo<F, V>
+    where F: Float,
+          V: Vector<F>,
+          Standard: Distribution<F>,
+          Standard: Distribution<V>,
+{
+    fn next<R>(&mut self, poisson: &mut Builder<F, V>, rng: &mut R) -> Option<V>
+        where R: Rng
+    {
+        match *self {
+            Algo::Bridson(ref mut a) => a.next(poisson, rng),
+            Algo::Ebeida(ref mut a) => a.next(poisson, rng),
+        }
+    }
+
+    fn size_hint(&self, poisson: &Builder<F, V>) -> (usize, Option<usize>) {
+        match *self {
+            Algo::Bridson(ref a) => a.size_hint(poisson),
+            Algo::Ebeida(ref a) => a.size_hint(poisson),
+        }
+    }
+
+    fn restrict(&mut self, sample: V) {
+        match *self {
+            Algo::Bridson(ref mut a) => a.restrict(sample),
+            Algo::Ebeida(ref mut a) => a.restrict(sample),
+        }
+    }
+
+    fn restrict_as_seed(&mut self, sample: V) {
+        match *self {
+            Algo::Bridson(ref mut a) => a.restrict_as_seed(sample),
+            Algo::Ebeida(ref mut a) => a.restrict_as_seed(sample),
+        }
+    }
+
+    fn restrict_with_radius(&mut self, sample: V, radius: F) -> bool {
+        match *self {
+            Algo::Bridson(ref mut a) => a.restrict_with_radius(sample, radius),
+            Algo::Ebeida(ref mut a) => a.restrict_with_radius(sample, radius),
+        }
+    }
+
+    fn stays_legal(&self, poisson: &Builder<F, V>, sample: V) -> bool {
+        match *self {
+            Algo::Bridson(ref a) => a.stays_legal(poisson, sample),
+            Algo::Ebeida(ref a) => a.stays_legal(poisson, sample),
+        }
+    }
+
+    fn conflicts(&self, poisson: &Builder<F, V>, sample: &V) -> Vec<V> {
+        match *self {
+            Algo::Bridson(ref a) => a.conflicts(poisson, sample),
+            Algo::Ebeida(ref a) => a.conflicts(poisson, sample),
+        }
+    }
+
+    fn remove(&mut self, poisson: &Builder<F, V>, sample: V) -> bool {
+        match *self {
+            Algo::Bridson(ref mut a) => a.remove(poisson, sample),
+            Algo::Ebeida(ref mut a) => a.remove(poisson, sample),
+        }
+    }
+
+    fn remove_region<P>(&mut self, poisson: &Builder<F, V>, region: P) -> Vec<V>
+        where P: Fn(&V) -> bool
+    {
+        match *self {
+            Algo::Bridson(ref mut a) => a.remove_region(poisson, region),
+            Algo::Ebeida(ref mut a) => a.remove_region(poisson, region),
+        }
+    }
+
+    fn restrict_obstacle(&mut self, obstacle: Obstacle<F, V>, distance: F) -> bool {
+        match *self {
+            Algo::Bridson(ref mut a) => a.restrict_obstacle(obstacle, distance),
+            Algo::Ebeida(ref mut a) => a.restrict_obstacle(obstacle, distance),
+        }
+    }
+
+    fn parent(&self) -> Option<usize> {
+        match *self {
+            Algo::Bridson(ref a) => a.parent(),
+            Algo::Ebeida(ref a) => a.parent(),
+        }
+    }
+
+    fn level(&self) -> Option<usize> {
+        match *self {
+            Algo::Bridson(ref a) => a.level(),
+            Algo::Ebeida(ref a) => a.level(),
+        }
+    }
+}
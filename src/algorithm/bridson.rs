@@ -1,5 +1,6 @@
 use {Builder, Vector, Float};
 use algorithm::{Creator, Algorithm};
+use obstacle::Obstacle;
 use utils::*;
 
 use rand::Rng;
@@ -7,8 +8,13 @@ use rand::distributions::{Distribution, Standard, Uniform};
 
 use sphere::sphere_volume;
 
+use std::collections::HashMap;
+
 /// Generates approximately uniform non-maximal Poisson-disk distribution with O(n) time and O(n) space complexity relative to the number of samples generated.
 /// Based on Bridson, Robert. "Fast Poisson disk sampling in arbitrary dimensions." SIGGRAPH Sketches. 2007.
+/// If the algorithm was restricted, the grid is scanned in random order for empty cells once there are no active samples left.
+/// The empty cells are then used as new seeds, so the whole domain gets covered even around the restrictions.
+/// After removing samples the space they freed is generated again without spreading outside of it.
 #[derive(Debug, Clone, Copy)]
 pub struct Bridson;
 
@@ -26,6 +32,13 @@ impl<F, V> Creator<F, V> for Bridson
             active_samples: vec![],
             outside: vec![],
             success: 0,
+            generated: 0,
+            parent: None,
+            restricted: false,
+            gaps: None,
+            freed: vec![],
+            refilled: vec![],
+            indices: HashMap::new(),
         }
     }
 }
@@ -37,9 +50,16 @@ pub struct Algo<F, V>
 
 {
     grid: Grid<F, V>,
-    active_samples: Vec<V>,
-    outside: Vec<V>,
+    active_samples: Vec<(V, Option<usize>, bool)>,
+    outside: Vec<(V, F)>,
     success: usize,
+    generated: usize,
+    parent: Option<usize>,
+    restricted: bool,
+    gaps: Option<Vec<usize>>,
+    freed: Vec<usize>,
+    refilled: Vec<usize>,
+    indices: HashMap<usize, Vec<(V, usize)>>,
 }
 
 impl<F, V> Algorithm<F, V> for Algo<F, V>
@@ -54,31 +74,64 @@ impl<F, V> Algorithm<F, V> for Algo<F, V>
     {
         while !self.active_samples.is_empty() {
             let index = rng.sample(Uniform::new(0, self.active_samples.len()));
-            let cur = self.active_samples[index].clone();
+            let (cur, parent, refilling) = self.active_samples[index].clone();
             for _ in 0..30 {
                 let min = F::cast(2) * poisson.radius;
                 let max = F::cast(4) * poisson.radius;
                 let sample = cur.clone() + random_point_annulus(rng, min, max);
                 if (0..V::dimension()).map(|n| sample[n]).all(|c| F::cast(0) <= c && c <= F::cast(1)) {
+                    let sample = snap(poisson, sample);
                     let index = sample_to_index(&sample, self.grid.side());
-                    if self.insert_if_valid(poisson, index, sample.clone()) {
+                    // Samples that refill space freed by removing samples stay within that space.
+                    if (!refilling || self.is_refilled(poisson, &index)) &&
+                       self.insert_if_valid(poisson, index, sample.clone(), refilling) {
+                        self.parent = parent;
                         return Some(sample);
                     }
                 }
             }
             self.active_samples.swap_remove(index);
         }
-        while self.success == 0 {
+        // Restrictions can block the whole domain, so restricted grids are seeded by scanning the gaps instead.
+        while self.success == 0 && !self.restricted {
             let cell = rng.sample(Uniform::new(0, self.grid.cells()));
             let index: V = decode(cell, self.grid.side())
                                .expect("Because we are decoding random index within grid \
                                         this should work.");
-            let sample = choose_random_sample(rng, &self.grid, index.clone(), 0);
-            if self.insert_if_valid(poisson, index, sample.clone()) {
+            let sample = snap(poisson, choose_random_sample(rng, &self.grid, index.clone(), 0));
+            let index = snapped_index(poisson, index, &sample, self.grid.side());
+            if self.insert_if_valid(poisson, index, sample.clone(), false) {
+                self.parent = None;
+                return Some(sample);
+            }
+        }
+        // Fills gaps that spawning from the active samples didn't reach.
+        while let Some(cell) = self.freed.pop() {
+            if let Some(sample) = self.fill_gap(poisson, rng, cell, true) {
+                return Some(sample);
+            }
+        }
+        // Freed space is refilled once there are no active samples or freed cells left.
+        self.refilled.clear();
+        // Without restrictions spawning from the seed covers the domain, so only restricted grids are scanned.
+        if !self.restricted {
+            return None;
+        }
+        if self.gaps.is_none() {
+            self.gaps = Some((0..self.grid.cells()).collect());
+        }
+        loop {
+            let cell = match self.gaps {
+                Some(ref mut gaps) if !gaps.is_empty() => {
+                    let index = rng.sample(Uniform::new(0, gaps.len()));
+                    gaps.swap_remove(index)
+                }
+                _ => return None,
+            };
+            if let Some(sample) = self.fill_gap(poisson, rng, cell, false) {
                 return Some(sample);
             }
         }
-        None
     }
 
     fn size_hint(&self, poisson: &Builder<F, V>) -> (usize, Option<usize>) {
@@ -106,19 +159,81 @@ impl<F, V> Algorithm<F, V> for Algo<F, V>
     }
 
     fn restrict(&mut self, sample: V) {
+        self.restrict_with_radius(sample, F::cast(0));
+    }
+
+    fn restrict_as_seed(&mut self, sample: V) {
+        let inside = (0..V::dimension()).all(|n| F::cast(0) <= sample[n] && sample[n] <= F::cast(1));
+        self.restrict(sample.clone());
+        if inside {
+            self.active_samples.push((sample, None, false));
+        }
+    }
+
+    fn restrict_with_radius(&mut self, sample: V, radius: F) -> bool {
+        self.restricted = true;
         self.success += 1;
         let index = sample_to_index(&sample, self.grid.side());
-        if let Some(g) = self.grid.get_mut(index) {
-            g.push(sample);
-        } else {
-            self.outside.push(sample);
+        if !self.grid.insert(index, sample.clone(), radius) {
+            self.outside.push((sample, radius));
         }
+        true
     }
 
     fn stays_legal(&self, poisson: &Builder<F, V>, sample: V) -> bool {
         let index = sample_to_index(&sample, self.grid.side());
         is_disk_free(&self.grid, poisson, index, 0, sample.clone(), &self.outside)
     }
+
+    fn conflicts(&self, poisson: &Builder<F, V>, sample: &V) -> Vec<V> {
+        conflicts(&self.grid, poisson, &self.outside, sample)
+    }
+
+    fn remove(&mut self, poisson: &Builder<F, V>, sample: V) -> bool {
+        let found = match self.grid.remove(&sample) {
+            Some(radius) => Some(radius),
+            None => self.outside
+                .iter()
+                .position(|v| same(&v.0, &sample))
+                .map(|i| self.outside.swap_remove(i).1),
+        };
+        if let Some(radius) = found {
+            self.success -= 1;
+            self.active_samples.retain(|a| !same(&a.0, &sample));
+            let side = self.grid.side();
+            if let Some(cell) = encode(&sample_to_index(&sample, side), side, poisson.poisson_type)
+                .and_then(|cell| self.indices.get_mut(&cell)) {
+                cell.retain(|i| !same(&i.0, &sample));
+            }
+            self.reactivate(poisson, &[(sample, radius)]);
+        }
+        found.is_some()
+    }
+
+    fn remove_region<P>(&mut self, poisson: &Builder<F, V>, region: P) -> Vec<V>
+        where P: Fn(&V) -> bool
+    {
+        let mut removed = self.grid.remove_where(&region);
+        removed.extend(self.outside.iter().filter(|v| region(&v.0)).cloned());
+        self.outside.retain(|v| !region(&v.0));
+        self.success -= removed.len();
+        self.active_samples.retain(|a| !region(&a.0));
+        for cell in self.indices.values_mut() {
+            cell.retain(|i| !region(&i.0));
+        }
+        self.reactivate(poisson, &removed);
+        removed.into_iter().map(|v| v.0).collect()
+    }
+
+    fn restrict_obstacle(&mut self, obstacle: Obstacle<F, V>, distance: F) -> bool {
+        self.restricted = true;
+        self.grid.obstruct(obstacle, distance);
+        true
+    }
+
+    fn parent(&self) -> Option<usize> {
+        self.parent
+    }
 }
 
 impl<F, V> Algo<F, V>
@@ -126,24 +241,97 @@ impl<F, V> Algo<F, V>
           V: Vector<F>,
 
 {
-    fn insert_if_valid(&mut self, poisson: &mut Builder<F, V>, index: V, sample: V) -> bool {
+    fn insert_if_valid(&mut self, poisson: &mut Builder<F, V>, index: V, sample: V, refilling: bool) -> bool {
         if is_disk_free(&self.grid,
                         poisson,
                         index.clone(),
                         0,
                         sample.clone(),
                         &self.outside) {
-            self.active_samples.push(sample.clone());
-            self.grid
-                .get_mut(index)
-                .expect("Because the sample is [0, 1] indexing it should work.")
-                .push(sample);
+            self.active_samples.push((sample.clone(), Some(self.generated), refilling));
+            if let Some(cell) = encode(&index, self.grid.side(), poisson.poisson_type) {
+                self.indices.entry(cell).or_default().push((sample.clone(), self.generated));
+            }
+            self.generated += 1;
+            assert!(self.grid.insert(index, sample, F::cast(0)),
+                    "Because the sample is [0, 1] indexing it should work.");
             self.success += 1;
             true
         } else {
             false
         }
     }
+
+    /// Checks if the cell is within the space freed by removing samples.
+    fn is_refilled(&self, poisson: &Builder<F, V>, index: &V) -> bool {
+        match encode(index, self.grid.side(), poisson.poisson_type) {
+            Some(cell) => self.refilled.binary_search(&cell).is_ok(),
+            None => false,
+        }
+    }
+
+    /// Tries to place a seed to the cell if it's empty and not covered by a single sample.
+    fn fill_gap<R>(&mut self, poisson: &mut Builder<F, V>, rng: &mut R, cell: usize, refilling: bool) -> Option<V>
+        where R: Rng,
+              Standard: Distribution<V>,
+    {
+        let side = self.grid.side();
+        let index: V = decode(cell, side).expect("Because the cell is within grid decoding it should work.");
+        let empty = self.grid
+            .get(index.clone())
+            .expect("Because the index was decoded from the grid indexing it should work.")
+            .is_empty();
+        let spacing = F::cast(1) / F::cast(side);
+        if empty && !snapped_cell_within(&self.grid, poisson, &index, spacing, F::cast(2) * poisson.radius) {
+            for _ in 0..30 {
+                let sample = snap(poisson, index_to_sample(&(index.clone() + rng.gen()), side));
+                let index = snapped_index(poisson, index.clone(), &sample, side);
+                if self.insert_if_valid(poisson, index, sample.clone(), refilling) {
+                    self.parent = None;
+                    return Some(sample);
+                }
+            }
+        }
+        None
+    }
+
+    /// Returns the index of the generated sample, which is unknown for the samples restricted to the algorithm.
+    fn generated_index(&self, poisson: &Builder<F, V>, sample: &V) -> Option<usize> {
+        let side = self.grid.side();
+        encode(&sample_to_index(sample, side), side, poisson.poisson_type)
+            .and_then(|cell| self.indices.get(&cell))
+            .and_then(|cell| cell.iter().find(|i| same(&i.0, sample)))
+            .map(|i| i.1)
+    }
+
+    /// Makes samples that can spawn samples into the space freed by removing samples active again.
+    /// Samples refilling the freed space are limited to the cells near the removed samples, so they don't spread to gaps elsewhere.
+    /// Samples spawned from the re-activated samples keep them as their parents.
+    /// Cells near the removed samples are checked for gaps again.
+    fn reactivate(&mut self, poisson: &Builder<F, V>, removed: &[(V, F)]) {
+        let distance = F::cast(2) * poisson.radius;
+        for &(ref r, radius) in removed {
+            let freed = distance.max(radius);
+            let cells = self.grid.cells_within(r, freed);
+            self.refilled.extend(cells.iter().cloned());
+            self.freed.extend(cells);
+            let reach = freed + distance;
+            let sqreach = reach.powi(2);
+            let neighbours = self.grid
+                .neighbours(r, reach)
+                .filter(|v| sqdist(v.0.clone(), r.clone(), poisson.poisson_type) < sqreach)
+                .map(|v| v.0.clone())
+                .collect::<Vec<_>>();
+            for v in neighbours {
+                if !self.active_samples.iter().any(|a| same(&a.0, &v)) {
+                    let index = self.generated_index(poisson, &v);
+                    self.active_samples.push((v, index, true));
+                }
+            }
+        }
+        self.refilled.sort();
+        self.refilled.dedup();
+    }
 }
 
 fn random_point_annulus<F, V, R>(rand: &mut R, min: F, max: F) -> V
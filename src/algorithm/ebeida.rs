@@ -1,5 +1,6 @@
 use {Builder, Vector, Float};
 use algorithm::{Creator, Algorithm};
+use obstacle::Obstacle;
 use utils::*;
 
 use rand::Rng;
@@ -23,6 +24,7 @@ impl<F, V> Creator<F, V> for Ebeida
     fn create(poisson: &Builder<F, V>) -> Self::Algo {
         let dim = V::dimension();
         let grid = Grid::new(poisson.radius, poisson.poisson_type);
+        let side = grid.side();
         let mut indices = Vec::with_capacity(grid.cells() * dim);
         let choices = (0..grid.side()).collect::<Vec<_>>();
         indices.extend(each_combination(&choices));
@@ -44,10 +46,12 @@ impl<F, V> Creator<F, V> for Ebeida
             level: 0,
             success: 0,
             outside: vec![],
-            mantissa_digits: {
+            max_level: {
+                // Indices of the cells have to be exactly representable by floats at every level.
                 let (mantissa, _, _) = F::max_value().integer_decode();
-                mantissa.count_ones() as usize
-            }
+                let bits = usize::MAX.count_ones() - side.leading_zeros();
+                (mantissa.count_ones() as usize).saturating_sub(bits as usize + 1)
+            },
         }
     }
 }
@@ -64,8 +68,8 @@ pub struct Algo<F, V>
     range: Uniform<usize>,
     throws: usize,
     success: usize,
-    outside: Vec<V>,
-    mantissa_digits: usize,
+    outside: Vec<(V, F)>,
+    max_level: usize,
     a: f64,
 }
 
@@ -78,75 +82,49 @@ impl<F, V> Algorithm<F, V> for Algo<F, V>
     fn next<R>(&mut self, poisson: &mut Builder<F, V>, rng: &mut R) -> Option<V>
         where R: Rng
     {
-        if self.indices.is_empty() {
-            return None;
-        }
-        while self.level < self.mantissa_digits {
-            while self.throws > 0 {
+        while self.level < self.max_level {
+            while self.throws > 0 && !self.indices.is_empty() {
                 self.throws -= 1;
                 let index = rng.sample(self.range);
                 let cur = self.indices[index].clone();
-                let parent = get_parent(cur.clone(), self.level);
-                if !self.grid
-                        .get(parent.clone())
-                        .expect("Indexing base grid by valid parent failed.")
-                        .is_empty() {
-                    self.indices.swap_remove(index);
-                    if self.indices.is_empty() {
-                        return None;
-                    }
-                    self.range = Uniform::new(0, self.indices.len());
-                } else {
-                    let sample = choose_random_sample(rng, &self.grid, cur.clone(), self.level);
-                    if is_disk_free(&self.grid,
-                                    poisson,
-                                    cur.clone(),
-                                    self.level,
-                                    sample.clone(),
-                                    &self.outside) {
-                        self.grid
-                            .get_mut(parent)
-                            .expect("Indexing base grid by already indexed valid parent failed.")
-                            .push(sample.clone());
+                let spacing = self.spacing();
+                let sample = snap(poisson, (cur.clone() + rng.gen()) * spacing);
+                if self.insert_if_valid(poisson, &cur, sample.clone()) {
+                    // Cells of the base level can be large enough to fit more than one sample.
+                    if snapped_cell_within(&self.grid, poisson, &cur, spacing, F::cast(2) * poisson.radius) {
                         self.indices.swap_remove(index);
                         if !self.indices.is_empty() {
                             self.range = Uniform::new(0, self.indices.len());
                         }
-                        self.success += 1;
-                        return Some(sample);
                     }
+                    return Some(sample);
                 }
             }
-            self.subdivide(&poisson);
+            self.subdivide(poisson);
+            self.level += 1;
             if self.indices.is_empty() {
                 return None;
             }
             self.range = Uniform::new(0, self.indices.len());
             self.throws = (self.a * self.indices.len() as f64).ceil() as usize;
-            self.level += 1;
         }
-        let index = rng.sample(self.range);
-        let cur = self.indices.swap_remove(index);
-        let side = 2usize.pow(self.level as u32);
-        let sample = index_to_sample(&cur, side);
-        if is_disk_free(&self.grid,
-                        poisson,
-                        cur.clone(),
-                        self.level,
-                        sample.clone(),
-                        &self.outside) {
-            Some(sample)
-        } else {
-            None
+        // Cells can't be divided any further, so every remaining one is tried until one of them is free.
+        while !self.indices.is_empty() {
+            let index = rng.sample(Uniform::new(0, self.indices.len()));
+            let cur = self.indices.swap_remove(index);
+            let sample = snap(poisson, (cur.clone() + rng.gen()) * self.spacing());
+            if self.insert_if_valid(poisson, &cur, sample.clone()) {
+                return Some(sample);
+            }
         }
+        None
     }
 
     fn size_hint(&self, poisson: &Builder<F, V>) -> (usize, Option<usize>) {
         // Calculating lower bound should work because we calculate how much volume is left to be filled at worst case and
         // how much sphere can fill it at best case and just figure out how many fills are still needed.
         let dim = V::dimension();
-        let side = 2usize.pow(self.level as u32);
-        let spacing = self.grid.cell() / F::cast(side);
+        let spacing = self.spacing();
         let grid_volume = F::cast(self.indices.len()) * spacing.powi(dim as i32);
         let sphere_volume = sphere_volume(F::cast(2) * poisson.radius, dim as u64);
         let lower = grid_volume / sphere_volume;
@@ -158,24 +136,73 @@ impl<F, V> Algorithm<F, V> for Algo<F, V>
             lower -= 1;
         }
         // Calculating upper bound should work because there is this many places left in the grid and no more can fit into it.
-        let upper = self.grid.cells() - self.success;
+        // Halves of the cells of the base level are always small enough to fit only a single sample.
+        let diagonal = F::cast(dim).sqrt() / F::cast(self.grid.side());
+        let capacity = if diagonal < F::cast(2) * poisson.radius {
+            1
+        } else {
+            2usize.pow(dim as u32)
+        };
+        let upper = (self.grid.cells() * capacity).saturating_sub(self.success);
         (lower, Some(upper))
     }
 
     fn restrict(&mut self, sample: V) {
+        self.restrict_with_radius(sample, F::cast(0));
+    }
+
+    fn restrict_with_radius(&mut self, sample: V, radius: F) -> bool {
         self.success += 1;
         let index = sample_to_index(&sample, self.grid.side());
-        if let Some(g) = self.grid.get_mut(index) {
-            g.push(sample);
-        } else {
-            self.outside.push(sample);
+        if !self.grid.insert(index, sample.clone(), radius) {
+            self.outside.push((sample, radius));
         }
+        true
     }
 
     fn stays_legal(&self, poisson: &Builder<F, V>, sample: V) -> bool {
         let index = sample_to_index(&sample, self.grid.side());
         is_disk_free(&self.grid, poisson, index, 0, sample.clone(), &self.outside)
     }
+
+    fn conflicts(&self, poisson: &Builder<F, V>, sample: &V) -> Vec<V> {
+        conflicts(&self.grid, poisson, &self.outside, sample)
+    }
+
+    fn remove(&mut self, poisson: &Builder<F, V>, sample: V) -> bool {
+        let found = match self.grid.remove(&sample) {
+            Some(radius) => Some(radius),
+            None => self.outside
+                .iter()
+                .position(|v| same(&v.0, &sample))
+                .map(|i| self.outside.swap_remove(i).1),
+        };
+        if let Some(radius) = found {
+            self.success -= 1;
+            self.reactivate(poisson, &[(sample, radius)]);
+        }
+        found.is_some()
+    }
+
+    fn remove_region<P>(&mut self, poisson: &Builder<F, V>, region: P) -> Vec<V>
+        where P: Fn(&V) -> bool
+    {
+        let mut removed = self.grid.remove_where(&region);
+        removed.extend(self.outside.iter().filter(|v| region(&v.0)).cloned());
+        self.outside.retain(|v| !region(&v.0));
+        self.success -= removed.len();
+        self.reactivate(poisson, &removed);
+        removed.into_iter().map(|v| v.0).collect()
+    }
+
+    fn restrict_obstacle(&mut self, obstacle: Obstacle<F, V>, distance: F) -> bool {
+        self.grid.obstruct(obstacle, distance);
+        true
+    }
+
+    fn level(&self) -> Option<usize> {
+        Some(self.level)
+    }
 }
 
 impl<F, V> Algo<F, V>
@@ -183,39 +210,101 @@ impl<F, V> Algo<F, V>
           V: Vector<F>,
 
 {
+    /// Returns the width of the cells at the current level.
+    fn spacing(&self) -> F {
+        F::cast(1) / (F::cast(self.grid.side()) * F::cast(2).powi(self.level as i32))
+    }
+
+    fn insert_if_valid(&mut self, poisson: &Builder<F, V>, index: &V, sample: V) -> bool {
+        let parent = snapped_index(poisson, get_parent(index.clone(), self.level), &sample, self.grid.side());
+        if is_disk_free(&self.grid,
+                        poisson,
+                        parent.clone(),
+                        0,
+                        sample.clone(),
+                        &self.outside) {
+            assert!(self.grid.insert(parent, sample, F::cast(0)),
+                    "Indexing base grid by valid parent failed.");
+            self.success += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Restarts generation from the base level with the uncovered cells near the removed samples.
+    /// Cells that are still waiting to be sampled are moved back to the base level too.
+    fn reactivate(&mut self, poisson: &Builder<F, V>, removed: &[(V, F)]) {
+        let side = self.grid.side();
+        let poisson_type = poisson.poisson_type;
+        let level = self.level;
+        let mut cells = self.indices
+            .drain(..)
+            .filter_map(|i| encode(&get_parent(i, level), side, poisson_type))
+            .collect::<Vec<_>>();
+        for &(ref r, radius) in removed {
+            cells.extend(self.grid.cells_within(r, radius.max(F::cast(2) * poisson.radius)));
+        }
+        cells.sort();
+        cells.dedup();
+        let (grid, outside) = (&self.grid, &self.outside);
+        let spacing = F::cast(1) / F::cast(side);
+        self.indices.extend(cells.into_iter()
+            .map(|c| decode(c, side).expect("Cell was encoded from valid index."))
+            .filter(|i: &V| !covered(grid, poisson, outside, i, spacing)));
+        self.level = 0;
+        if !self.indices.is_empty() {
+            self.range = Uniform::new(0, self.indices.len());
+        }
+        self.throws = (self.a * self.indices.len() as f64).ceil() as usize;
+    }
+
     fn subdivide(&mut self, poisson: &Builder<F, V>) {
         let choices = &[0, 1];
-        let (grid, outside, level) = (&self.grid, &self.outside, self.level);
+        let (grid, outside) = (&self.grid, &self.outside);
+        let spacing = self.spacing() / F::cast(2);
         self.indices.flat_map_inplace(|i| {
+            // Halves of the cell that are snapped to the same stratum contain the same snapped points,
+            // so only the lower one of them is kept.
+            let collapsed = if poisson.strata.is_empty() {
+                vec![]
+            } else {
+                let parts = snapped_parts(poisson, &i, spacing * F::cast(2));
+                let first = parts[0][0].clone();
+                (0..V::dimension())
+                    .filter(|&n| parts.iter().flatten().all(|c| c[n] == first[n]))
+                    .collect()
+            };
             each_combination(choices)
+                .filter(move |n: &V| collapsed.iter().all(|&c| n[c] == F::cast(0)))
                 .map(move |n: V| n + i.clone() * F::cast(2))
-                .filter(|c| !covered(grid, poisson, outside, c.clone(), level + 1))
+                .filter(|c| !covered(grid, poisson, outside, c, spacing))
         });
     }
 }
 
+/// Checks if the whole cell is covered by the disk of a single sample or by an obstacle.
+/// Cells covered only by multiple disks together are left for the next levels, where they are split
+/// until each of their parts is covered by a single disk.
+/// With strata the points of the cell are snapped before checking if they are covered by disks.
 fn covered<F, V>(grid: &Grid<F, V>,
                  poisson: &Builder<F, V>,
-                 outside: &[V],
-                 index: V,
-                 level: usize)
+                 outside: &[(V, F)],
+                 index: &V,
+                 spacing: F)
                  -> bool
     where F: Float,
           V: Vector<F>,
 
 {
-    // TODO: This does 4^d checking of points even though it could be done 3^d
-    let side = 2usize.pow(level as u32);
-    let spacing = grid.cell() / F::cast(side);
-    let sqradius = (F::cast(2) * poisson.radius).powi(2);
-    let parent = get_parent(index.clone(), level);
-    each_combination(&[0, 1])
-        .map(|t| (index.clone() + t) * spacing)
-        .all(|t| {
-            each_combination(&[-2, -1, 0, 1, 2])
-                .filter_map(|t| grid.get(parent.clone() + t))
-                .flat_map(|t| t)
-                .any(|v| sqdist(v.clone(), t.clone(), poisson.poisson_type) < sqradius) ||
-            !is_valid(poisson, &outside, t)
-        })
+    let distance = F::cast(2) * poisson.radius;
+    snapped_cell_within(grid, poisson, index, spacing, distance) ||
+    grid.cell_blocked(index, spacing) ||
+    outside.iter().any(|&(ref s, radius)| {
+        let sqdistance = distance.max(radius).powi(2);
+        snapped_parts(poisson, index, spacing)
+            .into_iter()
+            .flatten()
+            .all(|c| sqdist(s.clone(), c, poisson.poisson_type) <= sqdistance)
+    })
 }
@@ -1,14 +1,17 @@
 //! Module that contains traits that describe poisson-disk distribution generating algorithms.
 
 use {Builder, Vector, Float};
+use obstacle::Obstacle;
 
 use rand::Rng;
 
 use std::fmt::Debug;
 
+pub use self::auto::{Auto, Choice, Quality};
 pub use self::bridson::Bridson;
 pub use self::ebeida::Ebeida;
 
+mod auto;
 mod bridson;
 mod ebeida;
 
@@ -23,6 +26,12 @@ pub trait Creator<F, V>: Copy + Debug
 
     /// Creates new and empty algorithm instance.
     fn create(&Builder<F, V>) -> Self::Algo;
+
+    /// Creates new and empty algorithm instance using the configuration of the creator.
+    /// By default creators have no configuration and this is same as `create`.
+    fn create_from(&self, poisson: &Builder<F, V>) -> Self::Algo {
+        Self::create(poisson)
+    }
 }
 
 /// Trait that describes poisson-disk distribution generating algorithm.
@@ -40,6 +49,57 @@ pub trait Algorithm<F, V>
     /// Restricts the algorithm with an arbitary sample.
     fn restrict(&mut self, V);
 
+    /// Restricts the algorithm with an arbitary sample that the algorithm can grow the distribution from.
+    /// By default this is same as `restrict`.
+    fn restrict_as_seed(&mut self, sample: V) {
+        self.restrict(sample);
+    }
+
+    /// Restricts the algorithm with an arbitary point that samples have to be at least the radius away from,
+    /// or the distance between samples if it's larger.
+    /// Returns if the algorithm supports it, which it doesn't by default, so the point is ignored.
+    fn restrict_with_radius(&mut self, _sample: V, _radius: F) -> bool {
+        false
+    }
+
     /// Checks if a sample is valid for the poisson-disk distribution generated thus far by the algorithm.
     fn stays_legal(&self, &Builder<F, V>, V) -> bool;
+
+    /// Restricts the algorithm with an obstacle that samples have to be at least the distance away from.
+    /// Returns if the algorithm supports it, which it doesn't by default, so the obstacle is ignored.
+    fn restrict_obstacle(&mut self, _obstacle: Obstacle<F, V>, _distance: F) -> bool {
+        false
+    }
+
+    /// Returns the samples of the distribution that are too close to the sample.
+    /// By default the algorithm doesn't support finding them and none are returned.
+    fn conflicts(&self, _poisson: &Builder<F, V>, _sample: &V) -> Vec<V> {
+        vec![]
+    }
+
+    /// Removes a sample from the distribution and makes the space it freed available for generation again.
+    /// Returns if the sample was found and removed.
+    /// By default the algorithm doesn't support removing samples and nothing is removed.
+    fn remove(&mut self, _poisson: &Builder<F, V>, _sample: V) -> bool {
+        false
+    }
+
+    /// Removes all samples within the region from the distribution and makes the space they freed available for generation again.
+    /// Returns the removed samples.
+    /// By default the algorithm doesn't support removing samples and nothing is removed.
+    fn remove_region<P>(&mut self, _poisson: &Builder<F, V>, _region: P) -> Vec<V> where P: Fn(&V) -> bool {
+        vec![]
+    }
+
+    /// Returns the index of the generated sample the last generated sample was spawned from.
+    /// Samples are indexed in the order they were generated and seeds have no parent.
+    fn parent(&self) -> Option<usize> {
+        None
+    }
+
+    /// Returns the level of grid subdivision at which the last generated sample was accepted.
+    /// Algorithms that don't subdivide the grid have no levels.
+    fn level(&self) -> Option<usize> {
+        None
+    }
 }
@@ -0,0 +1,569 @@
+//! Module that contains baseline samplers to compare poisson-disk distribution against.
+//!
+//! All of the samplers generate samples in [0, 1[<sup>d</sup> through iterators yielding vectors,
+//! so same code can consume them and the poisson-disk generators uniformly.
+//!
+//! ````rust
+//! # extern crate poisson;
+//! # extern crate rand;
+//! # extern crate nalgebra as na;
+//! # use poisson::{Builder, Type, algorithm};
+//! # use poisson::baseline::Halton;
+//! # use rand::FromEntropy;
+//! # use rand::rngs::SmallRng;
+//! fn spread<I: IntoIterator<Item = na::Vector2<f64>>>(samples: I) -> f64 {
+//!     let samples = samples.into_iter().collect::<Vec<_>>();
+//!     let mut min = std::f64::MAX;
+//!     for (i, a) in samples.iter().enumerate() {
+//!         for b in &samples[i + 1..] {
+//!             min = min.min((a - b).norm());
+//!         }
+//!     }
+//!     min
+//! }
+//!
+//! fn main() {
+//!     let poisson = Builder::<_, na::Vector2<f64>>::with_samples(100, 0.8, Type::Normal)
+//!         .build(SmallRng::from_entropy(), algorithm::Ebeida);
+//!     println!("{}", spread(poisson));
+//!     println!("{}", spread(Halton::new(100).iter(SmallRng::from_entropy())));
+//! }
+//! ````
+
+use {Vector, Float};
+
+use rand::Rng;
+use rand::distributions::{Distribution, Standard};
+use rand::seq::SliceRandom;
+
+use std::marker::PhantomData;
+
+/// Samples independently and uniformly distributed in the space.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct WhiteNoise {
+    samples: usize,
+}
+
+impl WhiteNoise {
+    /// New white noise sampler with amount of samples specified.
+    pub fn new(samples: usize) -> Self {
+        WhiteNoise { samples }
+    }
+
+    /// Returns the amount of samples generated.
+    pub fn samples(&self) -> usize {
+        self.samples
+    }
+
+    /// Returns iterator that generates the samples.
+    pub fn iter<F, V, R>(&self, rng: R) -> WhiteNoiseIter<F, V, R>
+        where F: Float,
+              V: Vector<F>,
+              R: Rng,
+    {
+        WhiteNoiseIter {
+            rng,
+            remaining: self.samples,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Generates the samples.
+    pub fn generate<F, V, R>(&self, rng: &mut R) -> Vec<V>
+        where F: Float,
+              V: Vector<F>,
+              R: Rng,
+              Standard: Distribution<V>,
+    {
+        self.iter(rng).collect()
+    }
+}
+
+/// Iterator for generating white noise.
+pub struct WhiteNoiseIter<F, V, R>
+    where F: Float,
+          V: Vector<F>,
+          R: Rng,
+{
+    rng: R,
+    remaining: usize,
+    _marker: PhantomData<(F, V)>,
+}
+
+impl<F, V, R> Iterator for WhiteNoiseIter<F, V, R>
+    where F: Float,
+          V: Vector<F>,
+          R: Rng,
+          Standard: Distribution<V>,
+{
+    type Item = V;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+        Some(self.rng.gen())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+/// Samples placed randomly one per cell of a regular grid.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Jittered {
+    side: usize,
+}
+
+impl Jittered {
+    /// New jittered sampler with amount of cells per side of the grid specified.
+    /// The side should be larger than 0 and it generates side<sup>d</sup> samples.
+    pub fn new(side: usize) -> Self {
+        assert!(side > 0);
+        Jittered { side }
+    }
+
+    /// Returns the amount of cells per side of the grid.
+    pub fn side(&self) -> usize {
+        self.side
+    }
+
+    /// Returns iterator that generates the samples.
+    pub fn iter<F, V, R>(&self, rng: R) -> JitteredIter<F, V, R>
+        where F: Float,
+              V: Vector<F>,
+              R: Rng,
+    {
+        JitteredIter {
+            rng,
+            side: self.side,
+            cur: 0,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Generates the samples.
+    pub fn generate<F, V, R>(&self, rng: &mut R) -> Vec<V>
+        where F: Float,
+              V: Vector<F>,
+              R: Rng,
+              Standard: Distribution<V>,
+    {
+        self.iter(rng).collect()
+    }
+}
+
+/// Iterator for generating jittered grid.
+pub struct JitteredIter<F, V, R>
+    where F: Float,
+          V: Vector<F>,
+          R: Rng,
+{
+    rng: R,
+    side: usize,
+    cur: usize,
+    _marker: PhantomData<(F, V)>,
+}
+
+impl<F, V, R> Iterator for JitteredIter<F, V, R>
+    where F: Float,
+          V: Vector<F>,
+          R: Rng,
+          Standard: Distribution<V>,
+{
+    type Item = V;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.cur >= self.side.pow(V::dimension() as u32) {
+            return None;
+        }
+        let mut sample: V = self.rng.gen();
+        let mut div = self.cur;
+        for n in 0..V::dimension() {
+            sample[n] = (sample[n] + F::cast(div % self.side)) / F::cast(self.side);
+            div /= self.side;
+        }
+        self.cur += 1;
+        Some(sample)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.side.pow(V::dimension() as u32) - self.cur;
+        (remaining, Some(remaining))
+    }
+}
+
+/// Samples which projections to each axis are stratified so that there is exactly one sample per stratum.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LatinHypercube {
+    samples: usize,
+}
+
+impl LatinHypercube {
+    /// New latin hypercube sampler with amount of samples specified.
+    pub fn new(samples: usize) -> Self {
+        LatinHypercube { samples }
+    }
+
+    /// Returns the amount of samples generated.
+    pub fn samples(&self) -> usize {
+        self.samples
+    }
+
+    /// Returns iterator that generates the samples.
+    pub fn iter<F, V, R>(&self, mut rng: R) -> LatinHypercubeIter<F, V, R>
+        where F: Float,
+              V: Vector<F>,
+              R: Rng,
+    {
+        let strata = (0..V::dimension())
+            .map(|_| {
+                let mut stratum = (0..self.samples).collect::<Vec<_>>();
+                stratum.shuffle(&mut rng);
+                stratum
+            })
+            .collect();
+        LatinHypercubeIter {
+            rng,
+            strata,
+            cur: 0,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Generates the samples.
+    pub fn generate<F, V, R>(&self, rng: &mut R) -> Vec<V>
+        where F: Float,
+              V: Vector<F>,
+              R: Rng,
+              Standard: Distribution<V>,
+    {
+        self.iter(rng).collect()
+    }
+}
+
+/// Iterator for generating latin hypercube samples.
+pub struct LatinHypercubeIter<F, V, R>
+    where F: Float,
+          V: Vector<F>,
+          R: Rng,
+{
+    rng: R,
+    strata: Vec<Vec<usize>>,
+    cur: usize,
+    _marker: PhantomData<(F, V)>,
+}
+
+impl<F, V, R> Iterator for LatinHypercubeIter<F, V, R>
+    where F: Float,
+          V: Vector<F>,
+          R: Rng,
+          Standard: Distribution<V>,
+{
+    type Item = V;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let samples = self.strata.first().map_or(0, Vec::len);
+        if self.cur >= samples {
+            return None;
+        }
+        let mut sample: V = self.rng.gen();
+        for n in 0..V::dimension() {
+            sample[n] = (sample[n] + F::cast(self.strata[n][self.cur])) / F::cast(samples);
+        }
+        self.cur += 1;
+        Some(sample)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.strata.first().map_or(0, Vec::len) - self.cur;
+        (remaining, Some(remaining))
+    }
+}
+
+/// Halton low-discrepancy sequence using first d primes as bases.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Halton {
+    samples: usize,
+    rotate: bool,
+}
+
+impl Halton {
+    /// New Halton sequence with amount of samples specified.
+    pub fn new(samples: usize) -> Self {
+        Halton {
+            samples,
+            rotate: false,
+        }
+    }
+
+    /// Sets if the sequence is randomised by shifting it toroidally by random vector.
+    pub fn set_rotate(&mut self, rotate: bool) {
+        self.rotate = rotate;
+    }
+
+    /// Returns the amount of samples generated.
+    pub fn samples(&self) -> usize {
+        self.samples
+    }
+
+    /// Returns iterator that generates the samples.
+    pub fn iter<F, V, R>(&self, mut rng: R) -> HaltonIter<F, V>
+        where F: Float,
+              V: Vector<F>,
+              R: Rng,
+              Standard: Distribution<V>,
+    {
+        HaltonIter {
+            bases: primes(V::dimension()),
+            shift: if self.rotate { rng.gen() } else { V::zero() },
+            cur: 0,
+            samples: self.samples,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Generates the samples.
+    pub fn generate<F, V, R>(&self, rng: &mut R) -> Vec<V>
+        where F: Float,
+              V: Vector<F>,
+              R: Rng,
+              Standard: Distribution<V>,
+    {
+        self.iter(rng).collect()
+    }
+}
+
+/// Iterator for generating Halton sequence.
+pub struct HaltonIter<F, V>
+    where F: Float,
+          V: Vector<F>,
+{
+    bases: Vec<usize>,
+    shift: V,
+    cur: usize,
+    samples: usize,
+    _marker: PhantomData<F>,
+}
+
+impl<F, V> Iterator for HaltonIter<F, V>
+    where F: Float,
+          V: Vector<F>,
+{
+    type Item = V;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.cur >= self.samples {
+            return None;
+        }
+        let mut sample = V::zero();
+        for n in 0..V::dimension() {
+            sample[n] = wrap(radical_inverse::<F>(self.cur, self.bases[n]) + self.shift[n]);
+        }
+        self.cur += 1;
+        Some(sample)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.samples - self.cur;
+        (remaining, Some(remaining))
+    }
+}
+
+// Primitive polynomials and initial direction numbers for dimensions 2 to 10.
+// Based on Joe, Stephen, and Frances Y. Kuo. "Constructing Sobol sequences with better two-dimensional projections." SIAM Journal on Scientific Computing 30.5 (2008): 2635-2654.
+const SOBOL_POLYNOMIALS: [(u32, u32, &[u32]); 9] = [
+    (1, 0, &[1]),
+    (2, 1, &[1, 3]),
+    (3, 1, &[1, 3, 1]),
+    (3, 2, &[1, 1, 1]),
+    (4, 1, &[1, 1, 3, 3]),
+    (4, 4, &[1, 3, 5, 13]),
+    (5, 2, &[1, 1, 5, 5, 17]),
+    (5, 4, &[1, 1, 5, 5, 5]),
+    (5, 7, &[1, 1, 7, 11, 19]),
+];
+
+const SOBOL_BITS: usize = 32;
+
+/// Sobol low-discrepancy sequence.
+/// This is supported up to 10 dimensions.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Sobol {
+    samples: usize,
+    rotate: bool,
+}
+
+impl Sobol {
+    /// New Sobol sequence with amount of samples specified.
+    /// The amount of samples should be at most 2<sup>32</sup>.
+    pub fn new(samples: usize) -> Self {
+        assert!((samples as u64) <= 1 << SOBOL_BITS);
+        Sobol {
+            samples,
+            rotate: false,
+        }
+    }
+
+    /// Sets if the sequence is randomised by shifting it toroidally by random vector.
+    pub fn set_rotate(&mut self, rotate: bool) {
+        self.rotate = rotate;
+    }
+
+    /// Returns the amount of samples generated.
+    pub fn samples(&self) -> usize {
+        self.samples
+    }
+
+    /// Returns iterator that generates the samples.
+    pub fn iter<F, V, R>(&self, mut rng: R) -> SobolIter<F, V>
+        where F: Float,
+              V: Vector<F>,
+              R: Rng,
+              Standard: Distribution<V>,
+    {
+        let dim = V::dimension();
+        assert!(dim <= SOBOL_POLYNOMIALS.len() + 1);
+        let directions = (0..dim)
+            .map(|n| {
+                if n == 0 {
+                    (0..SOBOL_BITS).map(|i| 1 << (SOBOL_BITS - 1 - i)).collect()
+                } else {
+                    sobol_directions(SOBOL_POLYNOMIALS[n - 1])
+                }
+            })
+            .collect();
+        SobolIter {
+            directions,
+            state: vec![0; dim],
+            shift: if self.rotate { rng.gen() } else { V::zero() },
+            cur: 0,
+            samples: self.samples,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Generates the samples.
+    pub fn generate<F, V, R>(&self, rng: &mut R) -> Vec<V>
+        where F: Float,
+              V: Vector<F>,
+              R: Rng,
+              Standard: Distribution<V>,
+    {
+        self.iter(rng).collect()
+    }
+}
+
+/// Iterator for generating Sobol sequence.
+pub struct SobolIter<F, V>
+    where F: Float,
+          V: Vector<F>,
+{
+    directions: Vec<Vec<u32>>,
+    state: Vec<u32>,
+    shift: V,
+    cur: usize,
+    samples: usize,
+    _marker: PhantomData<F>,
+}
+
+impl<F, V> Iterator for SobolIter<F, V>
+    where F: Float,
+          V: Vector<F>,
+{
+    type Item = V;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.cur >= self.samples {
+            return None;
+        }
+        let mut sample = V::zero();
+        for n in 0..V::dimension() {
+            let value = F::cast(self.state[n] as usize) / F::cast(2).powi(SOBOL_BITS as i32);
+            sample[n] = wrap(value + self.shift[n]);
+        }
+        // Gray code ordering allows advancing by flipping the direction number of the lowest zero bit.
+        let bit = (!self.cur).trailing_zeros() as usize;
+        if bit < SOBOL_BITS {
+            for (state, directions) in self.state.iter_mut().zip(&self.directions) {
+                *state ^= directions[bit];
+            }
+        }
+        self.cur += 1;
+        Some(sample)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.samples - self.cur;
+        (remaining, Some(remaining))
+    }
+}
+
+fn sobol_directions((degree, coefficients, initial): (u32, u32, &[u32])) -> Vec<u32> {
+    let degree = degree as usize;
+    let mut directions = Vec::with_capacity(SOBOL_BITS);
+    for (i, m) in initial.iter().enumerate() {
+        directions.push(m << (SOBOL_BITS - 1 - i));
+    }
+    for i in degree..SOBOL_BITS {
+        let mut direction = directions[i - degree] ^ (directions[i - degree] >> degree);
+        for k in 1..degree {
+            if (coefficients >> (degree - 1 - k)) & 1 == 1 {
+                direction ^= directions[i - k];
+            }
+        }
+        directions.push(direction);
+    }
+    directions
+}
+
+fn radical_inverse<F>(mut index: usize, base: usize) -> F
+    where F: Float,
+{
+    let mut result = F::cast(0);
+    let mut scale = F::cast(1) / F::cast(base);
+    while index > 0 {
+        result += F::cast(index % base) * scale;
+        index /= base;
+        scale /= F::cast(base);
+    }
+    result
+}
+
+fn primes(amount: usize) -> Vec<usize> {
+    let mut primes: Vec<usize> = Vec::with_capacity(amount);
+    let mut candidate = 2;
+    while primes.len() < amount {
+        if primes.iter().all(|p| candidate % p != 0) {
+            primes.push(candidate);
+        }
+        candidate += 1;
+    }
+    primes
+}
+
+fn wrap<F>(value: F) -> F
+    where F: Float,
+{
+    if value >= F::cast(1) {
+        value - F::cast(1)
+    } else {
+        value
+    }
+}
+
+#[test]
+fn radical_inverse_works() {
+    let expected = [0., 0.5, 0.25, 0.75, 0.125];
+    for (i, &e) in expected.iter().enumerate() {
+        assert_eq!(e, radical_inverse::<f64>(i, 2));
+    }
+    assert_eq!(1. / 3. + 1. / 9., radical_inverse::<f64>(4, 3));
+}
+
+#[test]
+fn primes_are_primes() {
+    assert_eq!(vec![2, 3, 5, 7, 11, 13], primes(6));
+}
@@ -0,0 +1,284 @@
+//! Module that contains boundary-first generation of poisson-disk distributions for meshing.
+//!
+//! Samples are placed first on the corners of the domain, then on its edges, then on its faces and so on
+//! until finally the interior is filled. Each stage restricts the generator with samples of the previous stages,
+//! so the boundary is represented with the same spacing as the interior.
+//!
+//! ````rust
+//! # extern crate poisson;
+//! # extern crate rand;
+//! # extern crate nalgebra as na;
+//! # use poisson::{Builder, Type, algorithm};
+//! # use poisson::boundary::{BoundaryFirst, Domain};
+//! # use rand::FromEntropy;
+//! # use rand::rngs::SmallRng;
+//! fn main() {
+//!     let triangle = Domain::Polygon(vec![
+//!         na::Vector2::new(0.1, 0.1),
+//!         na::Vector2::new(0.9, 0.1),
+//!         na::Vector2::new(0.5, 0.9),
+//!     ]);
+//!     let generator = Builder::with_radius(0.02, Type::Normal)
+//!         .build(SmallRng::from_entropy(), algorithm::Ebeida);
+//!     for sample in BoundaryFirst::new(triangle).generate(generator) {
+//!         println!("{} {:?}", sample.dimension, sample.value);
+//!     }
+//! }
+//! ````
+
+use {Generator, PoissonIter, Type, Vector, Float};
+use algorithm::{Creator, Algorithm};
+use obstacle::{Obstacle, polygon_contains};
+
+use rand::Rng;
+use rand::distributions::{Distribution, Standard, Uniform};
+
+/// Amount of times the cells of the boundary elements are subdivided.
+const LEVELS: usize = 16;
+
+/// Domain of which boundary is sampled first.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Domain<V> {
+    /// The whole [0, 1]<sup>d</sup> box.
+    Box,
+    /// Two dimensional polygon within [0, 1]<sup>2</sup> given by its vertices in order.
+    Polygon(Vec<V>),
+}
+
+/// Sample tagged with the dimension of the boundary element it was placed on.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Placed<V> {
+    /// Dimension of the element, which is 0 for corners, 1 for edges and d for the interior.
+    pub dimension: usize,
+    /// Position of the sample.
+    pub value: V,
+}
+
+/// Generates poisson-disk distribution by sampling the boundary of the domain before its interior.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BoundaryFirst<V> {
+    domain: Domain<V>,
+    throws: f64,
+}
+
+impl<V> BoundaryFirst<V> {
+    /// New generator with the domain specified.
+    pub fn new(domain: Domain<V>) -> Self {
+        if let Domain::Polygon(ref vertices) = domain {
+            assert!(vertices.len() >= 3);
+        }
+        BoundaryFirst {
+            domain,
+            throws: 1.,
+        }
+    }
+
+    /// Sets the amount of samples thrown on boundary elements per cell at each level of subdivision.
+    pub fn set_throws(&mut self, throws: f64) {
+        assert!(throws > 0.);
+        self.throws = throws;
+    }
+
+    /// Returns the domain.
+    pub fn domain(&self) -> &Domain<V> {
+        &self.domain
+    }
+
+    /// Returns the amount of samples thrown on boundary elements per cell at each level of subdivision.
+    pub fn throws(&self) -> f64 {
+        self.throws
+    }
+
+    /// Generates samples with the generator starting from the lowest dimensional elements of the boundary.
+    /// Samples are ordered by the dimension of the element they were placed on.
+    /// Each element is sampled maximally with the samples of lower dimensional elements before moving to the next dimension.
+    /// The generator should be non-perioditic.
+    /// The interior is generated only within the domain, so it is covered up to its boundary
+    /// if the algorithm of the generator supports obstacles.
+    pub fn generate<F, R, A>(&self, generator: Generator<F, V, R, A>) -> Vec<Placed<V>>
+        where F: Float,
+              V: Vector<F>,
+              R: Rng,
+              A: Creator<F, V>,
+              Standard: Distribution<F>,
+    {
+        assert_eq!(Type::Normal, generator.poisson_type());
+        let mut iter = generator.into_iter();
+        let mut result = vec![];
+        let mut restricted = true;
+        match self.domain {
+            Domain::Box => {
+                let dim = V::dimension();
+                for k in 0..dim {
+                    for (fixed, values) in faces(dim, k) {
+                        let mut origin = V::zero();
+                        let mut spans = vec![];
+                        for n in 0..dim {
+                            if fixed & (1 << n) != 0 {
+                                origin[n] = F::cast((values >> n) & 1);
+                            } else {
+                                let mut span = V::zero();
+                                span[n] = F::cast(1);
+                                spans.push(span);
+                            }
+                        }
+                        self.fill(&mut iter, &mut result, &origin, &spans);
+                    }
+                }
+            }
+            Domain::Polygon(ref vertices) => {
+                assert_eq!(2, V::dimension());
+                for v in vertices {
+                    place(&mut iter, &mut result, 0, v.clone());
+                }
+                for (i, a) in vertices.iter().enumerate() {
+                    let b = &vertices[(i + 1) % vertices.len()];
+                    self.fill(&mut iter, &mut result, a, &[b.clone() - a.clone()]);
+                }
+                restricted = iter.restrict_domain(Obstacle::Polygon(vertices.clone()));
+            }
+        }
+        let dimension = V::dimension();
+        // Algorithms that don't support obstacles generate everywhere, so samples outside of the domain are dropped.
+        result.extend(iter.filter(|value| restricted || self.contains(value))
+            .map(|value| Placed { dimension, value }));
+        result
+    }
+
+    /// Checks if the sample is within the domain.
+    pub fn contains<F>(&self, sample: &V) -> bool
+        where F: Float,
+              V: Vector<F>,
+    {
+        match self.domain {
+            Domain::Box => (0..V::dimension()).all(|n| F::cast(0) <= sample[n] && sample[n] <= F::cast(1)),
+            Domain::Polygon(ref vertices) => polygon_contains(vertices, sample),
+        }
+    }
+
+    /// Samples maximally the element spanned by the vectors from the origin like the Ebeida algorithm does.
+    /// Cells of the element are thrown samples to and the ones that aren't covered by a single disk are subdivided,
+    /// until the cells can't be divided any further and every remaining one is tried once more.
+    fn fill<F, R, A>(&self, iter: &mut PoissonIter<F, V, R, A>, result: &mut Vec<Placed<V>>, origin: &V, spans: &[V])
+        where F: Float,
+              V: Vector<F>,
+              R: Rng,
+              A: Algorithm<F, V>,
+              Standard: Distribution<F>,
+    {
+        let dimension = spans.len();
+        let distance = F::cast(2) * iter.radius();
+        // Diagonals of the cells of the base level are at most the radius.
+        let sides = spans.iter()
+            .map(|s| {
+                (s.norm() * F::cast(dimension).sqrt() / iter.radius())
+                    .ceil()
+                    .to_usize()
+                    .expect("Amount of cells of the element should be castable to usize.")
+                    .max(1)
+            })
+            .collect::<Vec<_>>();
+        let mut cells = vec![vec![]];
+        for &side in &sides {
+            cells = cells.into_iter()
+                .flat_map(|c: Vec<usize>| {
+                    (0..side).map(move |i| {
+                        let mut c = c.clone();
+                        c.push(i);
+                        c
+                    })
+                })
+                .collect();
+        }
+        // Position of the point given in cells of the level.
+        let point = |cell: &[F], level: usize| {
+            let mut value = origin.clone();
+            for (n, s) in spans.iter().enumerate() {
+                let t = cell[n] / (F::cast(sides[n]) * F::cast(2).powi(level as i32));
+                value += s.clone() * t;
+            }
+            value
+        };
+        let covered = |result: &[Placed<V>], cell: &[usize], level: usize| {
+            let corners = (0..1usize << dimension)
+                .map(|corner| {
+                    let c = cell.iter()
+                        .enumerate()
+                        .map(|(n, &i)| F::cast(i + ((corner >> n) & 1)))
+                        .collect::<Vec<_>>();
+                    point(&c, level)
+                })
+                .collect::<Vec<_>>();
+            result.iter().any(|s| corners.iter().all(|c| (s.value.clone() - c.clone()).norm() <= distance))
+        };
+        let mut level = 0;
+        while level < LEVELS && !cells.is_empty() {
+            let throws = (self.throws * cells.len() as f64).ceil() as usize;
+            for _ in 0..throws {
+                let index = iter.rng().sample(Uniform::new(0, cells.len()));
+                let random = (0..dimension)
+                    .map(|n| F::cast(cells[index][n]) + iter.rng().gen())
+                    .collect::<Vec<_>>();
+                if place(iter, result, dimension, point(&random, level)) && covered(result, &cells[index], level) {
+                    cells.swap_remove(index);
+                    if cells.is_empty() {
+                        return;
+                    }
+                }
+            }
+            level += 1;
+            cells = cells.into_iter()
+                .flat_map(|c| {
+                    (0..1usize << dimension).map(move |child| {
+                        c.iter()
+                            .enumerate()
+                            .map(|(n, &i)| 2 * i + ((child >> n) & 1))
+                            .collect::<Vec<_>>()
+                    })
+                })
+                .filter(|c| !covered(result, c, level))
+                .collect();
+        }
+        for cell in cells {
+            let random = cell.iter()
+                .map(|&i| F::cast(i) + iter.rng().gen())
+                .collect::<Vec<_>>();
+            place(iter, result, dimension, point(&random, level));
+        }
+    }
+}
+
+/// Restricts the iterator with the sample if it stays legal and returns if it did.
+fn place<F, V, R, A>(iter: &mut PoissonIter<F, V, R, A>, result: &mut Vec<Placed<V>>, dimension: usize, value: V) -> bool
+    where F: Float,
+          V: Vector<F>,
+          R: Rng,
+          A: Algorithm<F, V>,
+{
+    if iter.stays_legal(value.clone()) {
+        iter.restrict(value.clone());
+        result.push(Placed { dimension, value });
+        true
+    } else {
+        false
+    }
+}
+
+/// Returns the k-dimensional faces of [0, 1]<sup>d</sup> as bitmasks of fixed axes and their values.
+fn faces(dim: usize, k: usize) -> Vec<(usize, usize)> {
+    let mut result = vec![];
+    for fixed in (0..1usize << dim).filter(|f| f.count_ones() as usize == dim - k) {
+        for values in (0..1usize << dim).filter(|v| v & !fixed == 0) {
+            result.push((fixed, values));
+        }
+    }
+    result
+}
+
+#[test]
+fn box_has_right_amount_of_faces() {
+    assert_eq!(8, faces(3, 0).len());
+    assert_eq!(12, faces(3, 1).len());
+    assert_eq!(6, faces(3, 2).len());
+    assert_eq!(1, faces(3, 3).len());
+}
@@ -0,0 +1,483 @@
+//! Module that contains Delaunay triangulation of generated distributions for meshing.
+//!
+//! Two dimensional distributions are triangulated and three dimensional ones are tetrahedralised with
+//! the Bowyer-Watson algorithm. Perioditic distributions are triangulated together with their neighbouring
+//! copies so that the triangulation wraps around the edges of the space.
+//! Quality statistics of the simplices show how the minimum distance of poisson-disk distribution
+//! prevents slivers that uniformly random points produce.
+//!
+//! This module is only available with the `delaunay` feature.
+//!
+//! ````rust
+//! # extern crate poisson;
+//! # extern crate rand;
+//! # extern crate nalgebra as na;
+//! # use poisson::{Builder, Type, algorithm};
+//! # use poisson::delaunay::triangulate;
+//! # use rand::FromEntropy;
+//! # use rand::rngs::SmallRng;
+//! fn main() {
+//!     let samples = Builder::<_, na::Vector2<f64>>::with_radius(0.05, Type::Normal)
+//!         .build(SmallRng::from_entropy(), algorithm::Ebeida)
+//!         .generate();
+//!     let triangulation = triangulate(&samples, Type::Normal);
+//!     for triangle in triangulation.simplices() {
+//!         println!("{:?}", triangle.indices);
+//!     }
+//!     println!("{:?}", triangulation.statistics());
+//! }
+//! ````
+
+use {Type, Vector, Float};
+use utils::*;
+
+use std::collections::HashMap;
+use std::f64;
+
+/// Triangle or tetrahedron of the triangulation.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Simplex<V> {
+    /// Indices of the samples that are the vertices of the simplex.
+    pub indices: Vec<usize>,
+    /// Positions of the vertices. For perioditic triangulation these can be copies of the samples
+    /// translated outside of [0, 1]<sup>d</sup> so that the simplex doesn't wrap around.
+    pub vertices: Vec<V>,
+}
+
+/// Quality statistics of the simplices of the triangulation.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Statistics<F> {
+    /// Amount of simplices.
+    pub simplices: usize,
+    /// Mean of ratios of circumradius to the shortest edge.
+    pub mean_radius_edge: F,
+    /// Largest ratio of circumradius to the shortest edge. Smallest possible is that of a regular simplex.
+    pub max_radius_edge: F,
+    /// Smallest angle in radians of any triangle. Only available for two dimensional triangulations.
+    pub min_angle: Option<F>,
+}
+
+/// Delaunay triangulation of samples.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Triangulation<F, V>
+    where F: Float,
+          V: Vector<F>,
+{
+    simplices: Vec<Simplex<V>>,
+    poisson_type: Type,
+    _marker: ::std::marker::PhantomData<F>,
+}
+
+impl<F, V> Triangulation<F, V>
+    where F: Float,
+          V: Vector<F>,
+{
+    /// Returns the simplices of the triangulation.
+    pub fn simplices(&self) -> &[Simplex<V>] {
+        &self.simplices
+    }
+
+    /// Returns the type of the triangulated distribution.
+    pub fn poisson_type(&self) -> Type {
+        self.poisson_type
+    }
+
+    /// Calculates the quality statistics of the simplices.
+    pub fn statistics(&self) -> Statistics<F> {
+        let mut sum = 0.;
+        let mut max: f64 = 0.;
+        let mut min_angle: f64 = f64::consts::PI;
+        for s in &self.simplices {
+            let points = s.vertices.iter().map(to_point).collect::<Vec<_>>();
+            let (_, sqradius) = circumsphere(&points, V::dimension());
+            let mut shortest = f64::INFINITY;
+            for (i, a) in points.iter().enumerate() {
+                for b in &points[i + 1..] {
+                    shortest = shortest.min(sqlength(a, b));
+                }
+            }
+            let ratio = (sqradius / shortest).sqrt();
+            sum += ratio;
+            max = max.max(ratio);
+            if V::dimension() == 2 {
+                for i in 0..3 {
+                    let (a, b, c) = (&points[i], &points[(i + 1) % 3], &points[(i + 2) % 3]);
+                    let (ab, ac, bc) = (sqlength(a, b), sqlength(a, c), sqlength(b, c));
+                    let cos = (ab + ac - bc) / (2. * (ab * ac).sqrt());
+                    min_angle = min_angle.min(cos.clamp(-1., 1.).acos());
+                }
+            }
+        }
+        let cast = |v: f64| -> F { ::num_traits::NumCast::from(v).expect("Statistic should be castable to float.") };
+        Statistics {
+            simplices: self.simplices.len(),
+            mean_radius_edge: cast(sum / self.simplices.len().max(1) as f64),
+            max_radius_edge: cast(max),
+            min_angle: if V::dimension() == 2 { Some(cast(min_angle)) } else { None },
+        }
+    }
+}
+
+/// Triangulates two dimensional or tetrahedralises three dimensional samples.
+/// Perioditic samples should be within [0, 1[<sup>d</sup> and dense enough for the triangulation to be local.
+pub fn triangulate<F, V>(samples: &[V], poisson_type: Type) -> Triangulation<F, V>
+    where F: Float,
+          V: Vector<F>,
+{
+    let dim = V::dimension();
+    assert!(dim == 2 || dim == 3);
+    // Perioditic copies are added only within a margin of a couple of sample spacings around the space.
+    // The margin is grown until it contains the circumspheres of the simplices around the samples,
+    // as then they are the same as if every copy had been added.
+    let spacing = (samples.len() as f64).powf(-1. / dim as f64);
+    let mut margin = (2. * spacing).min(1.);
+    loop {
+        let mut positions = samples.to_vec();
+        let mut origins = (0..samples.len()).collect::<Vec<_>>();
+        if poisson_type == Type::Perioditic {
+            for t in each_combination::<F, _, V>(&[-1, 0, 1]) {
+                if (0..dim).all(|n| t[n] == F::cast(0)) {
+                    continue;
+                }
+                for (i, s) in samples.iter().enumerate() {
+                    let p = s.clone() + t.clone();
+                    let within = (0..dim).all(|n| {
+                        let c = p[n].to_f64().expect("Coordinate should be castable to f64.");
+                        -margin <= c && c <= 1. + margin
+                    });
+                    if within {
+                        positions.push(p);
+                        origins.push(i);
+                    }
+                }
+            }
+        }
+        let points = positions.iter().map(to_point).collect::<Vec<_>>();
+        // Cospherical samples, like the copies of the same samples, are triangulated ambiguously. Tiny weights that are
+        // same for every copy of a sample break the ties so that every copy is triangulated the same way.
+        let weights = origins.iter()
+            .map(|&i| TIE_BREAKER * spacing * spacing * rank(i))
+            .collect::<Vec<_>>();
+        let cells = bowyer_watson(&points, &weights, dim);
+        let local = poisson_type == Type::Normal || margin >= 1. || cells.iter()
+            .filter(|c| c.vertices.iter().take(dim + 1).any(|&v| v < samples.len()))
+            .all(|c| {
+                let radius = c.sqradius.sqrt();
+                (0..dim).all(|n| -margin <= c.center[n] - radius && c.center[n] + radius <= 1. + margin)
+            });
+        if !local {
+            margin = (2. * margin).min(1.);
+            continue;
+        }
+        let simplices = cells.into_iter()
+            .filter(|c| c.vertices.iter().take(dim + 1).all(|&v| v < points.len()))
+            .filter(|c| {
+                // Every perioditic simplex has a copy for each translation, so only the one which
+                // lexicographically smallest vertex is within the space is kept.
+                poisson_type == Type::Normal || {
+                    let first = c.vertices
+                        .iter()
+                        .take(dim + 1)
+                        .map(|&i| &points[i])
+                        .fold(None, |m: Option<&[f64; 3]>, p| match m {
+                            Some(m) if m <= p => Some(m),
+                            _ => Some(p),
+                        })
+                        .expect("Simplices have vertices.");
+                    first.iter().take(dim).all(|c| (0. ..1.).contains(c))
+                }
+            })
+            .map(|c| Simplex {
+                indices: c.vertices.iter().take(dim + 1).map(|&i| origins[i]).collect(),
+                vertices: c.vertices.iter().take(dim + 1).map(|&i| positions[i].clone()).collect(),
+            })
+            .collect();
+        return Triangulation {
+            simplices,
+            poisson_type,
+            _marker: ::std::marker::PhantomData,
+        };
+    }
+}
+
+/// Simplex under construction with its circumsphere and the simplices across each of its faces.
+/// Face is identified by the vertex opposite to it.
+struct Cell {
+    vertices: [usize; 4],
+    neighbours: [Option<usize>; 4],
+    center: [f64; 3],
+    sqradius: f64,
+}
+
+/// Triangulates the weighted points returning the simplices with their orthospheres.
+/// Simplices that have vertices of the super simplex, which indices are past the points, are included.
+/// Each point is located by walking from the previously created simplex towards it,
+/// and the simplices which orthospheres contain it are found by searching through the neighbours from there.
+fn bowyer_watson(points: &[[f64; 3]], weights: &[f64], dim: usize) -> Vec<Cell> {
+    let n = points.len();
+    if n < dim + 1 {
+        return vec![];
+    }
+    // Super simplex that contains all of the points is built from a far away corner.
+    let (mut low, mut high) = (f64::INFINITY, f64::NEG_INFINITY);
+    for p in points {
+        for &c in p.iter().take(dim) {
+            low = low.min(c);
+            high = high.max(c);
+        }
+    }
+    let size = (high - low).max(1.) * 1000.;
+    let mut corner = [low - size; 3];
+    if dim == 2 {
+        corner[2] = 0.;
+    }
+    let mut all = points.to_vec();
+    all.push(corner);
+    for n in 0..dim {
+        let mut p = corner;
+        p[n] += SPAN * size;
+        all.push(p);
+    }
+    let mut weights = weights.to_vec();
+    weights.resize(all.len(), 0.);
+    let mut vertices = [0; 4];
+    for (i, v) in vertices.iter_mut().take(dim + 1).enumerate() {
+        *v = n + i;
+    }
+    let mut cells = vec![cell(&all, &weights, vertices, dim)];
+    // Index of the point which insertion removed the cell, or none for cells that are still part of the triangulation.
+    let mut removed = vec![None];
+    let mut free = vec![];
+    let mut last = 0;
+    // Points are inserted in pseudorandom order, as inserting many coplanar points first leaves
+    // the early simplices with the super simplex too flat for their circumspheres to be accurate.
+    let mut order = (0..n).collect::<Vec<_>>();
+    order.sort_by(|&a, &b| rank(a).partial_cmp(&rank(b)).expect("Ranks shouldn't be NaN."));
+    for p in order {
+        let point = &points[p];
+        let start = locate(&all, &cells, &removed, last, point, weights[p], dim);
+        removed[start] = Some(p);
+        let mut cavity = vec![start];
+        let mut i = 0;
+        while i < cavity.len() {
+            let c = cavity[i];
+            i += 1;
+            for f in 0..dim + 1 {
+                if let Some(neighbour) = cells[c].neighbours[f] {
+                    // Cospherical points make the circumsphere test ambiguous, so simplices behind faces that
+                    // the point doesn't clearly see are removed too to keep the new simplices from degenerating.
+                    let conflicts = sqlength(&cells[neighbour].center, point) - weights[p] < cells[neighbour].sqradius ||
+                                    barycentric(&all, &cells[c], f, point, dim) <= TOLERANCE;
+                    if removed[neighbour].is_none() && conflicts {
+                        removed[neighbour] = Some(p);
+                        cavity.push(neighbour);
+                    }
+                }
+            }
+        }
+        // Faces on the boundary of the cavity are connected to the point together with the slots of their outer neighbours.
+        let mut boundary = vec![];
+        for &c in &cavity {
+            for f in 0..dim + 1 {
+                let outer = match cells[c].neighbours[f] {
+                    Some(neighbour) if removed[neighbour].is_some() => continue,
+                    Some(neighbour) => {
+                        let slot = (0..dim + 1)
+                            .find(|&s| cells[neighbour].neighbours[s] == Some(c))
+                            .expect("Neighbours should point to each other.");
+                        Some((neighbour, slot))
+                    }
+                    None => None,
+                };
+                let mut vertices = cells[c].vertices;
+                vertices[f] = p;
+                boundary.push((vertices, f, outer));
+            }
+        }
+        free.extend(cavity);
+        let mut ridges: HashMap<[usize; 3], (usize, usize)> = HashMap::new();
+        for (vertices, f, outer) in boundary {
+            let mut new = cell(&all, &weights, vertices, dim);
+            new.neighbours[f] = outer.map(|(neighbour, _)| neighbour);
+            let index = match free.pop() {
+                Some(index) => {
+                    cells[index] = new;
+                    removed[index] = None;
+                    index
+                }
+                None => {
+                    cells.push(new);
+                    removed.push(None);
+                    cells.len() - 1
+                }
+            };
+            if let Some((neighbour, slot)) = outer {
+                cells[neighbour].neighbours[slot] = Some(index);
+            }
+            // New simplices are neighbours of each other across the faces that contain the point.
+            for k in (0..dim + 1).filter(|&k| k != f) {
+                let mut ridge = [usize::MAX; 3];
+                for (r, v) in ridge.iter_mut()
+                    .zip(vertices.iter().take(dim + 1).enumerate().filter(|&(j, _)| j != k).map(|(_, v)| v)) {
+                    *r = *v;
+                }
+                ridge.sort();
+                match ridges.remove(&ridge) {
+                    Some((other, face)) => {
+                        cells[index].neighbours[k] = Some(other);
+                        cells[other].neighbours[face] = Some(index);
+                    }
+                    None => {
+                        ridges.insert(ridge, (index, k));
+                    }
+                }
+            }
+            last = index;
+        }
+    }
+    cells.into_iter()
+        .zip(removed)
+        .filter(|&(_, removed)| removed.is_none())
+        .map(|(c, _)| c)
+        .collect()
+}
+
+/// Finds the simplex that contains the point by walking from the start through the faces the point is behind of.
+/// Falls back to searching every simplex for one which orthosphere contains the point if the walk doesn't end.
+fn locate(points: &[[f64; 3]], cells: &[Cell], removed: &[Option<usize>], start: usize, point: &[f64; 3], weight: f64, dim: usize)
+    -> usize
+{
+    let mut c = start;
+    for step in 0..cells.len() {
+        // Starting from different face at each step keeps the walk from cycling.
+        let behind = (0..dim + 1)
+            .map(|k| (k + step) % (dim + 1))
+            .find(|&f| barycentric(points, &cells[c], f, point, dim) < 0.);
+        match behind.and_then(|f| cells[c].neighbours[f]) {
+            Some(next) => c = next,
+            None => return c,
+        }
+    }
+    (0..cells.len())
+        .find(|&c| removed[c].is_none() && sqlength(&cells[c].center, point) - weight < cells[c].sqradius)
+        .expect("Some simplex should contain the point.")
+}
+
+/// Returns the barycentric coordinate of the point for the vertex of the simplex, which is negative if the point is
+/// on the other side of the opposite face than the vertex.
+fn barycentric(points: &[[f64; 3]], cell: &Cell, vertex: usize, point: &[f64; 3], dim: usize) -> f64 {
+    let mut simplex = [[0.; 3]; 4];
+    for (s, &v) in simplex.iter_mut().zip(&cell.vertices).take(dim + 1) {
+        *s = points[v];
+    }
+    let whole = orientation(&simplex, dim);
+    simplex[vertex] = *point;
+    orientation(&simplex, dim) / whole
+}
+
+/// Returns the determinant of the edges from the first vertex of the simplex, which sign tells its orientation.
+fn orientation(simplex: &[[f64; 3]; 4], dim: usize) -> f64 {
+    let edge = |i: usize, n: usize| simplex[i][n] - simplex[0][n];
+    if dim == 2 {
+        edge(1, 0) * edge(2, 1) - edge(1, 1) * edge(2, 0)
+    } else {
+        edge(1, 0) * (edge(2, 1) * edge(3, 2) - edge(2, 2) * edge(3, 1)) -
+        edge(1, 1) * (edge(2, 0) * edge(3, 2) - edge(2, 2) * edge(3, 0)) +
+        edge(1, 2) * (edge(2, 0) * edge(3, 1) - edge(2, 1) * edge(3, 0))
+    }
+}
+
+/// Barycentric coordinate below which the point is considered to be on the face instead of in front of it.
+const TOLERANCE: f64 = 1e-10;
+
+/// Largest weight of the samples relative to the squared spacing of the samples.
+const TIE_BREAKER: f64 = 1e-6;
+
+/// Returns pseudorandom number within [0, 1[ for the index of the sample.
+fn rank(index: usize) -> f64 {
+    let mut z = (index as u64).wrapping_add(0x9e37_79b9_7f4a_7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^= z >> 31;
+    (z >> 11) as f64 / (1u64 << 53) as f64
+}
+
+/// How many times the distance from the corner to the points the legs of the super simplex are.
+const SPAN: f64 = 10.;
+
+fn cell(points: &[[f64; 3]], weights: &[f64], vertices: [usize; 4], dim: usize) -> Cell {
+    let simplex = vertices.iter().take(dim + 1).map(|&v| points[v]).collect::<Vec<_>>();
+    let weights = vertices.iter().take(dim + 1).map(|&v| weights[v]).collect::<Vec<_>>();
+    let (center, sqradius) = orthosphere(&simplex, &weights, dim);
+    Cell {
+        vertices,
+        neighbours: [None; 4],
+        center,
+        sqradius,
+    }
+}
+
+/// Solves the center and squared radius of the sphere going through the vertices of the simplex.
+fn circumsphere(simplex: &[[f64; 3]], dim: usize) -> ([f64; 3], f64) {
+    orthosphere(simplex, &[0.; 4], dim)
+}
+
+/// Solves the center and squared radius of the sphere which power distance to each weighted vertex of the simplex is zero.
+/// Power distance of a point to the sphere is the squared distance to its center minus the squared radius and the weight of the point.
+fn orthosphere(simplex: &[[f64; 3]], weights: &[f64], dim: usize) -> ([f64; 3], f64) {
+    let origin = simplex[0];
+    // Rows of the augmented matrix are the equations 2 (p - o) . x = |p - o|^2 - (w_p - w_o).
+    let mut matrix = [[0.; 4]; 3];
+    for ((row, p), w) in matrix.iter_mut().zip(&simplex[1..]).zip(&weights[1..]) {
+        for n in 0..dim {
+            row[n] = 2. * (p[n] - origin[n]);
+        }
+        row[3] = sqlength(p, &origin) - (w - weights[0]);
+    }
+    for col in 0..dim {
+        let pivot = (col..dim)
+            .max_by(|&a, &b| matrix[a][col].abs().partial_cmp(&matrix[b][col].abs()).expect("Coordinates shouldn't be NaN."))
+            .expect("There is at least one row.");
+        matrix.swap(col, pivot);
+        if matrix[col][col].abs() < f64::EPSILON * 1e-3 {
+            return ([f64::INFINITY; 3], f64::INFINITY);
+        }
+        let pivot = matrix[col];
+        for (r, row) in matrix.iter_mut().enumerate().take(dim) {
+            if r != col {
+                let factor = row[col] / pivot[col];
+                for (value, p) in row.iter_mut().zip(&pivot).skip(col) {
+                    *value -= factor * p;
+                }
+            }
+        }
+    }
+    let mut center = [0.; 3];
+    for n in 0..dim {
+        center[n] = origin[n] + matrix[n][3] / matrix[n][n];
+    }
+    let sqradius = sqlength(&center, &origin) - weights[0];
+    (center, sqradius)
+}
+
+fn sqlength(a: &[f64; 3], b: &[f64; 3]) -> f64 {
+    a.iter().zip(b).map(|(a, b)| (a - b).powi(2)).sum()
+}
+
+fn to_point<F, V>(v: &V) -> [f64; 3]
+    where F: Float,
+          V: Vector<F>,
+{
+    let mut point = [0.; 3];
+    for (n, p) in point.iter_mut().enumerate().take(V::dimension()) {
+        *p = v[n].to_f64().expect("Coordinate should be castable to f64.");
+    }
+    point
+}
+
+#[test]
+fn circumsphere_of_right_triangle_is_at_hypotenuse() {
+    let (center, sqradius) = circumsphere(&[[0., 0., 0.], [2., 0., 0.], [0., 2., 0.]], 2);
+    assert_eq!([1., 1., 0.], center);
+    assert_eq!(2., sqradius);
+}
@@ -0,0 +1,314 @@
+//! Module that contains generation of tileable blue-noise dither masks.
+//!
+//! Masks are generated with the void-and-cluster method on a perioditic pixel grid.
+//! Each pixel gets an unique rank and pixels whose rank is below any threshold form a blue-noise distribution,
+//! so the mask can be used to dither with any threshold and tiled without seams.
+//!
+//! ````rust
+//! # extern crate poisson;
+//! # extern crate rand;
+//! # extern crate nalgebra as na;
+//! # use poisson::dither::VoidAndCluster;
+//! # use rand::FromEntropy;
+//! # use rand::rngs::SmallRng;
+//! fn main() {
+//!     let mask = VoidAndCluster::<_, na::Vector2<f64>>::new(32)
+//!         .generate(&mut SmallRng::from_entropy());
+//!     let texture: Vec<u8> = mask.to_u8();
+//!     println!("{:?}", texture);
+//! }
+//! ````
+
+use {Type, Vector, Float};
+use utils::*;
+
+use rand::Rng;
+use rand::seq::index;
+
+use std::marker::PhantomData;
+
+/// Generates dither masks with the void-and-cluster method.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct VoidAndCluster<F, V>
+    where F: Float,
+          V: Vector<F>,
+{
+    side: usize,
+    sigma: F,
+    initial: F,
+    _marker: PhantomData<V>,
+}
+
+impl<F, V> VoidAndCluster<F, V>
+    where F: Float,
+          V: Vector<F>,
+{
+    /// New generator with amount of pixels on each side of the mask specified.
+    pub fn new(side: usize) -> Self {
+        assert!(side > 1);
+        VoidAndCluster {
+            side,
+            sigma: F::cast(3) / F::cast(2),
+            initial: F::cast(1) / F::cast(10),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Sets the deviation of the gaussian filter used to find voids and clusters in pixels.
+    pub fn set_sigma(&mut self, sigma: F) {
+        assert!(F::cast(0) < sigma);
+        self.sigma = sigma;
+    }
+
+    /// Sets the fraction of pixels in the initial binary pattern.
+    pub fn set_initial(&mut self, initial: F) {
+        assert!(F::cast(0) < initial);
+        assert!(initial < F::cast(1));
+        self.initial = initial;
+    }
+
+    /// Returns the amount of pixels on each side of the mask.
+    pub fn side(&self) -> usize {
+        self.side
+    }
+
+    /// Returns the deviation of the gaussian filter in pixels.
+    pub fn sigma(&self) -> F {
+        self.sigma
+    }
+
+    /// Returns the fraction of pixels in the initial binary pattern.
+    pub fn initial(&self) -> F {
+        self.initial
+    }
+
+    /// Generates the dither mask.
+    pub fn generate<R>(&self, rng: &mut R) -> Mask
+        where R: Rng,
+    {
+        let dim = V::dimension();
+        let cells = self.side.pow(dim as u32);
+        let kernel = self.kernel();
+        let initial = (self.initial * F::cast(cells))
+            .round()
+            .to_usize()
+            .expect("Amount of initial pixels should be castable to usize.")
+            .max(1)
+            .min(cells - 1);
+        // Gaussian is negligible beyond four deviations, so energies are updated only within that reach.
+        let reach = (self.sigma * F::cast(4))
+            .ceil()
+            .to_usize()
+            .expect("Reach of the gaussian filter should be castable to usize.");
+        let window = window(self.side, dim, reach, &kernel);
+        let mut pattern = Pattern::new(self.side, dim, &window);
+        for p in index::sample(rng, cells, initial).into_iter() {
+            pattern.insert(p);
+        }
+        // Moves pixels from the tightest clusters to the largest voids until it doesn't change anything.
+        for _ in 0..cells {
+            let cluster = pattern.tightest_cluster();
+            pattern.remove(cluster);
+            let void = pattern.largest_void();
+            pattern.insert(void);
+            if cluster == void {
+                break;
+            }
+        }
+        let mut ranks = vec![0; cells];
+        let mut removing = pattern.clone();
+        for rank in (0..initial).rev() {
+            let cluster = removing.tightest_cluster();
+            removing.remove(cluster);
+            ranks[cluster] = rank;
+        }
+        for rank in initial..cells {
+            let void = pattern.largest_void();
+            pattern.insert(void);
+            ranks[void] = rank;
+        }
+        Mask {
+            side: self.side,
+            dimension: dim,
+            ranks,
+        }
+    }
+
+    /// Returns gaussian of the perioditic distance for each offset between pixels.
+    fn kernel(&self) -> Vec<F> {
+        let side = F::cast(self.side);
+        let two = F::cast(2);
+        let sigma = self.sigma / side;
+        (0..self.side.pow(V::dimension() as u32))
+            .map(|i| {
+                let mut offset = V::zero();
+                let mut rest = i;
+                for n in 0..V::dimension() {
+                    offset[n] = F::cast(rest % self.side) / side;
+                    rest /= self.side;
+                }
+                (-sqdist(V::zero(), offset, Type::Perioditic) / (two * sigma * sigma)).exp()
+            })
+            .collect()
+    }
+}
+
+/// Returns the offsets between pixels that are within the reach on each axis together with their gaussians.
+/// Offsets are given per axis and wrapped to [0, side), so each offset is listed once even if the reach covers the whole side.
+fn window<F>(side: usize, dim: usize, reach: usize, kernel: &[F]) -> Vec<(Vec<usize>, F)>
+    where F: Float,
+{
+    let axis = if 2 * reach + 1 >= side {
+        (0..side).collect::<Vec<_>>()
+    } else {
+        (0..reach + 1).chain(side - reach..side).collect()
+    };
+    let mut offsets = vec![vec![]];
+    for _ in 0..dim {
+        offsets = offsets.into_iter()
+            .flat_map(|offset: Vec<usize>| axis.iter().map(move |&o| {
+                let mut offset = offset.clone();
+                offset.push(o);
+                offset
+            }))
+            .collect();
+    }
+    offsets.into_iter()
+        .map(|offset| {
+            let index = offset.iter().rev().fold(0, |i, &o| i * side + o);
+            (offset, kernel[index])
+        })
+        .collect()
+}
+
+/// Binary pattern with gaussian energies of each pixel.
+#[derive(Clone)]
+struct Pattern<'a, F>
+    where F: Float + 'a,
+{
+    side: usize,
+    dim: usize,
+    window: &'a [(Vec<usize>, F)],
+    ones: Vec<bool>,
+    energy: Vec<F>,
+}
+
+impl<'a, F> Pattern<'a, F>
+    where F: Float,
+{
+    fn new(side: usize, dim: usize, window: &'a [(Vec<usize>, F)]) -> Self {
+        let cells = side.pow(dim as u32);
+        Pattern {
+            side,
+            dim,
+            window,
+            ones: vec![false; cells],
+            energy: vec![F::cast(0); cells],
+        }
+    }
+
+    fn insert(&mut self, pixel: usize) {
+        self.ones[pixel] = true;
+        self.update(pixel, F::cast(1));
+    }
+
+    fn remove(&mut self, pixel: usize) {
+        self.ones[pixel] = false;
+        self.update(pixel, -F::cast(1));
+    }
+
+    /// Adds the gaussian of the pixel to the energies of the pixels within the window around it.
+    fn update(&mut self, pixel: usize, sign: F) {
+        let mut coordinates = Vec::with_capacity(self.dim);
+        let mut rest = pixel;
+        for _ in 0..self.dim {
+            coordinates.push(rest % self.side);
+            rest /= self.side;
+        }
+        for &(ref offset, gaussian) in self.window {
+            let p = coordinates.iter()
+                .zip(offset)
+                .rev()
+                .fold(0, |i, (&c, &o)| i * self.side + (c + o) % self.side);
+            self.energy[p] += sign * gaussian;
+        }
+    }
+
+    fn tightest_cluster(&self) -> usize {
+        self.extreme(true, |a, b| a > b)
+    }
+
+    fn largest_void(&self) -> usize {
+        self.extreme(false, |a, b| a < b)
+    }
+
+    fn extreme<C>(&self, one: bool, better: C) -> usize
+        where C: Fn(F, F) -> bool,
+    {
+        let mut result = None;
+        for (p, &e) in self.energy.iter().enumerate() {
+            if self.ones[p] != one {
+                continue;
+            }
+            let improves = match result {
+                Some(r) => better(e, self.energy[r]),
+                None => true,
+            };
+            if improves {
+                result = Some(p);
+            }
+        }
+        result.expect("There should be pixel of both kinds.")
+    }
+}
+
+/// Dither mask where each pixel has an unique rank.
+/// Pixels are stored so that the first axis changes fastest.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Mask {
+    side: usize,
+    dimension: usize,
+    ranks: Vec<usize>,
+}
+
+impl Mask {
+    /// Returns the amount of pixels on each side of the mask.
+    pub fn side(&self) -> usize {
+        self.side
+    }
+
+    /// Returns the dimension of the mask.
+    pub fn dimension(&self) -> usize {
+        self.dimension
+    }
+
+    /// Returns the ranks of the pixels.
+    pub fn ranks(&self) -> &[usize] {
+        &self.ranks
+    }
+
+    /// Returns the rank of the pixel.
+    pub fn rank(&self, pixel: &[usize]) -> usize {
+        assert_eq!(self.dimension, pixel.len());
+        let index = pixel.iter().rev().fold(0, |i, &c| {
+            assert!(c < self.side);
+            i * self.side + c
+        });
+        self.ranks[index]
+    }
+
+    /// Returns the ranks scaled to thresholds of [0, 255].
+    pub fn to_u8(&self) -> Vec<u8> {
+        self.scaled(256).map(|r| r as u8).collect()
+    }
+
+    /// Returns the ranks scaled to thresholds of [0, 65535].
+    pub fn to_u16(&self) -> Vec<u16> {
+        self.scaled(65536).map(|r| r as u16).collect()
+    }
+
+    fn scaled(&self, levels: u64) -> impl Iterator<Item = u64> + '_ {
+        let cells = self.ranks.len() as u64;
+        self.ranks.iter().map(move |&r| r as u64 * levels / cells)
+    }
+}
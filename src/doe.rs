@@ -0,0 +1,215 @@
+//! Module that contains design of experiments sampling over typed axes.
+//!
+//! Each axis of the design has a type and a scale. Samples are generated in normalised space
+//! [0, 1[<sup>d</sup> where poisson-disk condition is checked, and decoded back to typed values.
+//! Integer and categorical axes are divided into equal sized strata of which centers represent the values,
+//! so distances between discrete values are also checked in the normalised space.
+//!
+//! ````rust
+//! # extern crate poisson;
+//! # extern crate rand;
+//! # extern crate nalgebra as na;
+//! # use poisson::{Builder, Type, algorithm};
+//! # use poisson::doe::{Axis, Design};
+//! # use rand::FromEntropy;
+//! # use rand::rngs::SmallRng;
+//! fn main() {
+//!     let design = Design::<_, na::Vector3<f64>>::new(vec![
+//!         Axis::Log(1e-5, 1e-1),  // learning rate
+//!         Axis::Integer(1, 8),    // layers
+//!         Axis::Categorical(3),   // optimizer
+//!     ]);
+//!     let generator = Builder::with_radius(0.1, Type::Normal)
+//!         .build(SmallRng::from_entropy(), algorithm::Ebeida);
+//!     for experiment in design.generate(generator) {
+//!         println!("{:?}", experiment);
+//!     }
+//! }
+//! ````
+
+use {Generator, Vector, Float};
+use algorithm::Creator;
+use utils::snap_to_strata;
+
+use num_traits::NumCast;
+use rand::Rng;
+
+use std::marker::PhantomData;
+
+/// Type and scale of an axis of the design.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Axis<F>
+    where F: Float,
+{
+    /// Real values in [min, max].
+    Continuous(F, F),
+    /// Real values in [min, max] scaled logarithmically. Both bounds should be positive.
+    Log(F, F),
+    /// Integer values in [min, max].
+    Integer(i64, i64),
+    /// Categories identified by indices in [0, amount[.
+    /// Categories are ordered by their index in the normalised space.
+    Categorical(usize),
+}
+
+/// Typed value of an axis.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Value<F>
+    where F: Float,
+{
+    /// Value of continuous or logarithmic axis.
+    Real(F),
+    /// Value of integer axis.
+    Integer(i64),
+    /// Index of the category of categorical axis.
+    Category(usize),
+}
+
+impl<F> Axis<F>
+    where F: Float,
+{
+    fn levels(&self) -> Option<usize> {
+        match *self {
+            Axis::Integer(min, max) => {
+                let levels = max.checked_sub(min)
+                    .and_then(|d| d.checked_add(1))
+                    .and_then(NumCast::from)
+                    .expect("Amount of values of integer axis should fit in usize.");
+                Some(levels)
+            }
+            Axis::Categorical(amount) => Some(amount),
+            _ => None,
+        }
+    }
+
+    /// Decodes coordinate of the normalised space to value of the axis.
+    pub fn decode(&self, coordinate: F) -> Value<F> {
+        let coordinate = coordinate.max(F::cast(0)).min(F::cast(1));
+        let level = |levels: usize| {
+            (coordinate * F::cast(levels))
+                .floor()
+                .to_usize()
+                .expect("Level of normalised coordinate should be castable to usize.")
+                .min(levels - 1)
+        };
+        match *self {
+            Axis::Continuous(min, max) => Value::Real(min + coordinate * (max - min)),
+            Axis::Log(min, max) => Value::Real((min.ln() + coordinate * (max.ln() - min.ln())).exp()),
+            Axis::Integer(min, _) => Value::Integer(min + level(self.levels().unwrap_or(1)) as i64),
+            Axis::Categorical(amount) => Value::Category(level(amount)),
+        }
+    }
+
+    /// Encodes value of the axis to coordinate of the normalised space.
+    /// Discrete values are encoded to the center of their stratum.
+    pub fn encode(&self, value: Value<F>) -> F {
+        let half = F::cast(1) / F::cast(2);
+        match (*self, value) {
+            (Axis::Continuous(min, max), Value::Real(v)) => (v - min) / (max - min),
+            (Axis::Log(min, max), Value::Real(v)) => (v.ln() - min.ln()) / (max.ln() - min.ln()),
+            (Axis::Integer(min, max), Value::Integer(v)) => {
+                assert!(min <= v && v <= max);
+                let levels = self.levels().unwrap_or(1);
+                (F::cast((v - min) as usize) + half) / F::cast(levels)
+            }
+            (Axis::Categorical(amount), Value::Category(v)) => {
+                assert!(v < amount);
+                (F::cast(v) + half) / F::cast(amount)
+            }
+            _ => panic!("Type of the value should match the type of the axis."),
+        }
+    }
+}
+
+/// Design of experiments with typed axes.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Design<F, V>
+    where F: Float,
+          V: Vector<F>,
+{
+    axes: Vec<Axis<F>>,
+    _marker: PhantomData<V>,
+}
+
+impl<F, V> Design<F, V>
+    where F: Float,
+          V: Vector<F>,
+{
+    /// New design with axes specified.
+    /// There should be as many axes as there are dimensions.
+    pub fn new(axes: Vec<Axis<F>>) -> Self {
+        assert_eq!(V::dimension(), axes.len());
+        for axis in &axes {
+            match *axis {
+                Axis::Continuous(min, max) => assert!(min < max),
+                Axis::Log(min, max) => assert!(F::cast(0) < min && min < max),
+                Axis::Integer(min, max) => assert!(min <= max && axis.levels().is_some()),
+                Axis::Categorical(amount) => assert!(amount > 0),
+            }
+        }
+        Design {
+            axes,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns the axes of the design.
+    pub fn axes(&self) -> &[Axis<F>] {
+        &self.axes
+    }
+
+    /// Decodes sample of the normalised space to typed values.
+    pub fn decode(&self, sample: &V) -> Vec<Value<F>> {
+        self.axes
+            .iter()
+            .enumerate()
+            .map(|(n, a)| a.decode(sample[n]))
+            .collect()
+    }
+
+    /// Encodes typed values to sample of the normalised space.
+    pub fn encode(&self, values: &[Value<F>]) -> V {
+        assert_eq!(self.axes.len(), values.len());
+        let mut result = V::zero();
+        for (n, (a, &v)) in self.axes.iter().zip(values).enumerate() {
+            result[n] = a.encode(v);
+        }
+        result
+    }
+
+    /// Moves discrete coordinates of the sample to the centers of their strata.
+    pub fn snap(&self, sample: &V) -> V {
+        snap_to_strata(&self.strata(), sample.clone())
+    }
+
+    /// Generates experiments with the generator.
+    /// Candidates of the generator are snapped to discrete values before checking that they are legal,
+    /// so the experiments fill the space of the discrete values.
+    pub fn generate<R, A>(&self, generator: Generator<F, V, R, A>) -> Vec<Vec<Value<F>>>
+        where R: Rng,
+              A: Creator<F, V>,
+    {
+        self.generate_normalised(generator)
+            .iter()
+            .map(|s| self.decode(s))
+            .collect()
+    }
+
+    /// Generates experiments with the generator returning them in the normalised space.
+    /// The generator has to support stratified generation, which `Bridson` and `Ebeida` do.
+    pub fn generate_normalised<R, A>(&self, mut generator: Generator<F, V, R, A>) -> Vec<V>
+        where R: Rng,
+              A: Creator<F, V>,
+    {
+        generator.stratify(self.strata());
+        generator.into_iter().collect()
+    }
+
+    /// Returns the amount of strata of each axis, which is 0 for continuous axes.
+    fn strata(&self) -> Vec<usize> {
+        self.axes
+            .iter()
+            .map(|a| a.levels().unwrap_or(0))
+            .collect()
+    }
+}
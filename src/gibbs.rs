@@ -0,0 +1,182 @@
+//! Module that contains Gibbs point processes where samples softly repel each other.
+//!
+//! Unlike poisson-disk distribution where disks of the samples can never intersect,
+//! samples of these processes are only penalised for being close to each other.
+//! The processes are simulated with birth-death Metropolis-Hastings algorithm.
+
+use {Type, Vector, Float};
+use utils::*;
+
+use rand::Rng;
+use rand::distributions::{Distribution, Standard, Uniform};
+
+use std::marker::PhantomData;
+
+/// Strauss process and hard-core Strauss process in [0, 1]<sup>d</sup>.
+///
+/// Density of the process is proportional to β<sup>n</sup>γ<sup>s</sup> where n is the amount of samples
+/// and s is the amount of pairs of samples that are closer than the interaction radius to each other.
+/// Hard-core variant additionally forbids pairs that are closer than the hard-core distance.
+/// Based on Møller, Jesper, and Rasmus Plenge Waagepetersen. "Statistical inference and simulation for spatial point processes." CRC Press, 2003.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Strauss<F, V>
+    where F: Float,
+          V: Vector<F>,
+{
+    intensity: F,
+    radius: F,
+    strength: F,
+    hard_core: F,
+    iterations: usize,
+    poisson_type: Type,
+    _marker: PhantomData<V>,
+}
+
+impl<F, V> Strauss<F, V>
+    where F: Float,
+          V: Vector<F>,
+          Standard: Distribution<V>,
+{
+    /// New Strauss process with intensity, interaction radius, interaction strength and type of the space specified.
+    /// The intensity should be larger than 0.
+    /// The interaction radius should be ]0, 1[.
+    /// The interaction strength should be [0, 1] where 0 forbids pairs closer than interaction radius
+    /// and 1 gives homogeneous poisson process.
+    pub fn new(intensity: F, radius: F, strength: F, poisson_type: Type) -> Self {
+        assert!(F::cast(0) < intensity);
+        assert!(F::cast(0) < radius);
+        assert!(radius < F::cast(1));
+        assert!(F::cast(0) <= strength);
+        assert!(strength <= F::cast(1));
+        Strauss {
+            intensity,
+            radius,
+            strength,
+            hard_core: F::cast(0),
+            iterations: 10_000,
+            poisson_type,
+            _marker: PhantomData,
+        }
+    }
+
+    /// New hard-core Strauss process with intensity, hard-core distance, interaction radius, interaction strength and type of the space specified.
+    /// The hard-core distance should be [0, interaction radius].
+    pub fn with_hard_core(intensity: F, hard_core: F, radius: F, strength: F, poisson_type: Type) -> Self {
+        assert!(F::cast(0) <= hard_core);
+        assert!(hard_core <= radius);
+        Strauss {
+            hard_core,
+            ..Strauss::new(intensity, radius, strength, poisson_type)
+        }
+    }
+
+    /// Sets the amount of birth-death steps done while simulating the process.
+    /// To reach equilibrium this should be considerably larger than the expected amount of samples.
+    pub fn set_iterations(&mut self, iterations: usize) {
+        self.iterations = iterations;
+    }
+
+    /// Returns the intensity of the process.
+    pub fn intensity(&self) -> F {
+        self.intensity
+    }
+
+    /// Returns the interaction radius of the process.
+    pub fn radius(&self) -> F {
+        self.radius
+    }
+
+    /// Returns the interaction strength of the process.
+    pub fn strength(&self) -> F {
+        self.strength
+    }
+
+    /// Returns the hard-core distance of the process.
+    pub fn hard_core(&self) -> F {
+        self.hard_core
+    }
+
+    /// Returns the amount of birth-death steps done while simulating the process.
+    pub fn iterations(&self) -> usize {
+        self.iterations
+    }
+
+    /// Returns the type of the space.
+    pub fn poisson_type(&self) -> Type {
+        self.poisson_type
+    }
+
+    /// Simulates the process starting from empty set of samples.
+    pub fn generate<R>(&self, rng: &mut R) -> Vec<V>
+        where R: Rng
+    {
+        self.simulate(rng, vec![])
+    }
+
+    /// Simulates the process starting from given samples.
+    /// Samples that are outside of [0, 1[<sup>d</sup> or violate the hard-core distance with earlier samples are dropped.
+    pub fn simulate<R>(&self, rng: &mut R, samples: Vec<V>) -> Vec<V>
+        where R: Rng
+    {
+        let mut grid = Grid::new(self.radius / F::cast(2), self.poisson_type);
+        let mut kept = Vec::with_capacity(samples.len());
+        for s in samples {
+            let inside = (0..V::dimension()).all(|n| F::cast(0) <= s[n] && s[n] < F::cast(1));
+            if !inside || self.interaction(&grid, &s, None).is_none() {
+                continue;
+            }
+            let index = sample_to_index(&s, grid.side());
+            assert!(grid.insert(index, s.clone(), F::cast(0)),
+                    "Because the sample is [0, 1[ indexing it should work.");
+            kept.push(s);
+        }
+        let mut samples = kept;
+        for _ in 0..self.iterations {
+            if rng.gen::<bool>() {
+                let sample: V = rng.gen();
+                if let Some(ratio) = self.interaction(&grid, &sample, None) {
+                    let ratio = self.intensity * ratio / F::cast(samples.len() + 1);
+                    if F::cast(1) <= ratio || rng.gen_bool(ratio.to_f64().unwrap_or(0.)) {
+                        let index = sample_to_index(&sample, grid.side());
+                        assert!(grid.insert(index, sample.clone(), F::cast(0)),
+                                "Because the sample is [0, 1[ indexing it should work.");
+                        samples.push(sample);
+                    }
+                }
+            } else if !samples.is_empty() {
+                let index = rng.sample(Uniform::new(0, samples.len()));
+                let ratio = self.interaction(&grid, &samples[index], Some(&samples[index]))
+                                .expect("Existing samples should respect the hard-core distance.");
+                let ratio = F::cast(samples.len()) / (self.intensity * ratio);
+                if F::cast(1) <= ratio || rng.gen_bool(ratio.to_f64().unwrap_or(1.)) {
+                    let sample = samples.swap_remove(index);
+                    grid.remove(&sample);
+                }
+            }
+        }
+        samples
+    }
+
+    /// Calculates γ<sup>t</sup> where t is the amount of samples within interaction radius of the sample.
+    /// Returns None if hard-core distance is violated.
+    fn interaction(&self, grid: &Grid<F, V>, sample: &V, ignore: Option<&V>) -> Option<F> {
+        let sqradius = self.radius.powi(2);
+        let sqhard_core = self.hard_core.powi(2);
+        let mut close = 0;
+        for (v, _) in grid.neighbours(sample, self.radius) {
+            if let Some(i) = ignore {
+                if same(i, v) {
+                    continue;
+                }
+            }
+            let dist = sqdist(v.clone(), sample.clone(), self.poisson_type);
+            if dist < sqhard_core {
+                return None;
+            }
+            if dist < sqradius {
+                close += 1;
+            }
+        }
+        Some(self.strength.powi(close))
+    }
+}
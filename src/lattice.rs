@@ -0,0 +1,284 @@
+//! Module that contains maximal poisson-disk sampling of sites of a discrete lattice.
+//!
+//! Samples are placed on integer coordinates of a lattice such as pixels or voxels.
+//! Instead of subdividing cells like the Ebeida algorithm, all sites that are still free are enumerated
+//! and samples are chosen uniformly from them, so the distribution is maximal over the lattice sites.
+//! Distances are compared as squared integers, so there are no rounding errors.
+//!
+//! ````rust
+//! # extern crate poisson;
+//! # extern crate rand;
+//! # use poisson::Type;
+//! # use poisson::lattice::Lattice;
+//! # use rand::FromEntropy;
+//! # use rand::rngs::SmallRng;
+//! fn main() {
+//!     // Tiles of 64x32 map which are at least 5 tiles apart.
+//!     let lattice = Lattice::new(vec![64, 32], 5, Type::Normal);
+//!     for site in lattice.iter(SmallRng::from_entropy()) {
+//!         println!("{:?}", site);
+//!     }
+//! }
+//! ````
+
+use {Type, Float};
+
+use rand::Rng;
+
+use modulo::Mod;
+
+/// Lattice of integer sites with minimum distance between samples.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Lattice {
+    size: Vec<usize>,
+    sqdistance: u64,
+    poisson_type: Type,
+}
+
+impl Lattice {
+    /// New lattice with size of each dimension and integer minimum distance between samples specified.
+    pub fn new(size: Vec<usize>, distance: u64, poisson_type: Type) -> Self {
+        Lattice::with_squared_distance(size, distance * distance, poisson_type)
+    }
+
+    /// New lattice with size of each dimension and real minimum distance between samples specified.
+    pub fn with_real_distance<F>(size: Vec<usize>, distance: F, poisson_type: Type) -> Self
+        where F: Float,
+    {
+        assert!(F::cast(0) < distance);
+        let sqdistance = distance.powi(2)
+            .ceil()
+            .to_u64()
+            .expect("Squared distance should be castable to u64.");
+        Lattice::with_squared_distance(size, sqdistance, poisson_type)
+    }
+
+    /// New lattice with size of each dimension and squared minimum distance between samples specified.
+    /// Samples are placed so that squared distance between any two samples is at least the squared distance.
+    pub fn with_squared_distance(size: Vec<usize>, sqdistance: u64, poisson_type: Type) -> Self {
+        assert!(!size.is_empty());
+        assert!(size.iter().all(|&s| s > 0));
+        assert!(sqdistance > 0);
+        Lattice {
+            size,
+            sqdistance,
+            poisson_type,
+        }
+    }
+
+    /// Returns the size of each dimension of the lattice.
+    pub fn size(&self) -> &[usize] {
+        &self.size
+    }
+
+    /// Returns the squared minimum distance between samples.
+    pub fn squared_distance(&self) -> u64 {
+        self.sqdistance
+    }
+
+    /// Returns the type of the distribution.
+    pub fn poisson_type(&self) -> Type {
+        self.poisson_type
+    }
+
+    /// Returns the amount of sites in the lattice.
+    pub fn sites(&self) -> usize {
+        self.size.iter().product()
+    }
+
+    /// Returns squared distance between two sites taking wrapping into account for perioditic lattice.
+    pub fn sqdist(&self, s1: &[usize], s2: &[usize]) -> u64 {
+        assert_eq!(self.size.len(), s1.len());
+        assert_eq!(self.size.len(), s2.len());
+        self.size
+            .iter()
+            .zip(s1.iter().zip(s2))
+            .map(|(&size, (&c1, &c2))| {
+                let d = c1.max(c2) - c1.min(c2);
+                let d = match self.poisson_type {
+                    Type::Perioditic => d.min(size - d),
+                    Type::Normal => d,
+                } as u64;
+                d * d
+            })
+            .sum()
+    }
+
+    /// Returns iterator that generates the samples.
+    pub fn iter<R>(&self, rng: R) -> LatticeIter<R>
+        where R: Rng,
+    {
+        let sites = self.sites();
+        LatticeIter {
+            rng,
+            offsets: self.offsets(),
+            lattice: self.clone(),
+            free: (0..sites).collect(),
+            position: (0..sites).collect(),
+        }
+    }
+
+    /// Generates the samples.
+    pub fn generate<R>(&self, rng: &mut R) -> Vec<Vec<usize>>
+        where R: Rng,
+    {
+        self.iter(rng).collect()
+    }
+
+    /// Returns all offsets which are closer than the minimum distance.
+    fn offsets(&self) -> Vec<Vec<i64>> {
+        let mut max = 0i64;
+        while (((max + 1) * (max + 1)) as u64) < self.sqdistance {
+            max += 1;
+        }
+        let mut result = vec![];
+        let mut offset = vec![-max; self.size.len()];
+        loop {
+            if offset.iter().map(|&o| (o * o) as u64).sum::<u64>() < self.sqdistance {
+                result.push(offset.clone());
+            }
+            match offset.iter().position(|&o| o < max) {
+                Some(n) => {
+                    offset[n] += 1;
+                    for o in &mut offset[..n] {
+                        *o = -max;
+                    }
+                }
+                None => return result,
+            }
+        }
+    }
+
+    fn encode(&self, site: &[usize]) -> Option<usize> {
+        assert_eq!(self.size.len(), site.len());
+        let mut index = 0;
+        for (&size, &c) in self.size.iter().zip(site).rev() {
+            if c >= size {
+                return None;
+            }
+            index = index * size + c;
+        }
+        Some(index)
+    }
+
+    fn decode(&self, mut index: usize) -> Vec<usize> {
+        self.size
+            .iter()
+            .map(|&size| {
+                let c = index % size;
+                index /= size;
+                c
+            })
+            .collect()
+    }
+}
+
+/// Iterator for generating maximal poisson-disk distribution on a lattice.
+pub struct LatticeIter<R>
+    where R: Rng,
+{
+    rng: R,
+    lattice: Lattice,
+    offsets: Vec<Vec<i64>>,
+    free: Vec<usize>,
+    position: Vec<usize>,
+}
+
+impl<R> Iterator for LatticeIter<R>
+    where R: Rng,
+{
+    type Item = Vec<usize>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.free.is_empty() {
+            return None;
+        }
+        let index = self.free[self.rng.gen_range(0, self.free.len())];
+        let site = self.lattice.decode(index);
+        self.block(&site);
+        Some(site)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.free.len().min(1), Some(self.free.len()))
+    }
+}
+
+impl<R> LatticeIter<R>
+    where R: Rng,
+{
+    /// Returns the lattice sampled.
+    pub fn lattice(&self) -> &Lattice {
+        &self.lattice
+    }
+
+    /// Returns the amount of sites where sample can still be placed.
+    pub fn remaining(&self) -> usize {
+        self.free.len()
+    }
+
+    /// Restricts the lattice with a site so that no samples are generated too close to it.
+    /// The site doesn't have to fulfill the minimum distance to other samples.
+    pub fn restrict(&mut self, site: &[usize]) {
+        assert!(self.lattice.encode(site).is_some());
+        self.block(site);
+    }
+
+    /// Checks if a site can be added to the distribution without breaking the minimum distance.
+    pub fn stays_legal(&self, site: &[usize]) -> bool {
+        match self.lattice.encode(site) {
+            Some(i) => self.position[i] != usize::MAX,
+            None => false,
+        }
+    }
+
+    /// Removes every site closer than the minimum distance from the free sites.
+    fn block(&mut self, site: &[usize]) {
+        for o in 0..self.offsets.len() {
+            let neighbour = self.lattice
+                .size
+                .iter()
+                .zip(site.iter().zip(&self.offsets[o]))
+                .map(|(&size, (&c, &o))| {
+                    let c = c as i64 + o;
+                    match self.lattice.poisson_type {
+                        Type::Perioditic => Some(c.modulo(size as i64) as usize),
+                        Type::Normal if 0 <= c && c < size as i64 => Some(c as usize),
+                        Type::Normal => None,
+                    }
+                })
+                .collect::<Option<Vec<_>>>();
+            if let Some(index) = neighbour.and_then(|n| self.lattice.encode(&n)) {
+                self.remove(index);
+            }
+        }
+    }
+
+    fn remove(&mut self, index: usize) {
+        let position = self.position[index];
+        if position == usize::MAX {
+            return;
+        }
+        self.free.swap_remove(position);
+        if let Some(&moved) = self.free.get(position) {
+            self.position[moved] = position;
+        }
+        self.position[index] = usize::MAX;
+    }
+}
+
+#[test]
+fn encoding_and_decoding_sites_match() {
+    let lattice = Lattice::new(vec![3, 4, 5], 1, Type::Normal);
+    for index in 0..lattice.sites() {
+        assert_eq!(Some(index), lattice.encode(&lattice.decode(index)));
+    }
+    assert_eq!(None, lattice.encode(&[3, 0, 0]));
+}
+
+#[test]
+fn offsets_are_strictly_within_distance() {
+    let lattice = Lattice::new(vec![10, 10], 2, Type::Normal);
+    // (0, 0), (±1, 0), (0, ±1) and (±1, ±1)
+    assert_eq!(9, lattice.offsets().len());
+}
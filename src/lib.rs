@@ -77,9 +77,28 @@ use std::marker::PhantomData;
 use std::ops::{AddAssign, SubAssign, MulAssign, DivAssign, Index, IndexMut};
 
 use algorithm::{Creator, Algorithm};
+use obstacle::Obstacle;
 use utils::math::calc_radius;
 
 pub mod algorithm;
+pub mod baseline;
+pub mod boundary;
+#[cfg(feature = "delaunay")]
+pub mod delaunay;
+pub mod dither;
+pub mod doe;
+pub mod gibbs;
+pub mod lattice;
+pub mod lod;
+pub mod obstacle;
+pub mod process;
+pub mod progressive;
+pub mod projection;
+pub mod spatial;
+pub mod spectrum;
+pub mod symmetry;
+pub mod temporal;
+pub mod well_spaced;
 mod utils;
 
 /// Describes what floats are.
@@ -129,6 +148,31 @@ impl Default for Type {
     }
 }
 
+/// Policy for handling samples that conflict with the distribution when restricting.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Policy {
+    /// Rejects conflicting samples.
+    Reject,
+    /// Inserts conflicting samples breaking the distribution.
+    Force,
+    /// Removes samples of the distribution that conflict with the sample before inserting it.
+    /// Sample is still rejected if it's illegal because of obstacles.
+    Evict,
+}
+
+/// Outcome of restricting a sample.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Restriction<V> {
+    /// The restricted sample.
+    pub value: V,
+    /// Whether the sample was inserted to the distribution.
+    pub inserted: bool,
+    /// Samples of the distribution that were too close to the sample.
+    pub conflicts: Vec<V>,
+    /// Samples that were removed from the distribution to make room for the sample.
+    pub evicted: Vec<V>,
+}
+
 /// Builder for the generator.
 #[derive(Default, Clone, Debug, PartialEq)]
 pub struct Builder<F, V>
@@ -137,6 +181,7 @@ pub struct Builder<F, V>
 {
     radius: F,
     poisson_type: Type,
+    strata: Vec<usize>,
     _marker: PhantomData<V>,
 }
 
@@ -153,6 +198,7 @@ impl<V, F> Builder<F, V>
         Builder {
             radius: radius,
             poisson_type: poisson_type,
+            strata: vec![],
             _marker: PhantomData,
         }
     }
@@ -166,6 +212,7 @@ impl<V, F> Builder<F, V>
             radius: relative *
                     NumCast::from(2f64.sqrt() / 2.).expect("Casting constant should always work."),
             poisson_type: poisson_type,
+            strata: vec![],
             _marker: PhantomData,
         }
     }
@@ -179,6 +226,7 @@ impl<V, F> Builder<F, V>
         Builder {
             radius: calc_radius::<F, V>(samples, relative, poisson_type),
             poisson_type: poisson_type,
+            strata: vec![],
             _marker: PhantomData,
         }
     }
@@ -194,11 +242,11 @@ impl<V, F> Builder<F, V>
     }
 
     /// Builds generator with random number generator and algorithm specified.
-    pub fn build<R, A>(self, rng: R, _algo: A) -> Generator<F, V, R, A>
+    pub fn build<R, A>(self, rng: R, algo: A) -> Generator<F, V, R, A>
         where R: Rng,
               A: Creator<F, V>
     {
-        Generator::new(self, rng)
+        Generator::new(self, rng, algo)
     }
 }
 
@@ -212,7 +260,7 @@ pub struct Generator<F, V, R, A>
 {
     poisson: Builder<F, V>,
     rng: R,
-    _algo: PhantomData<A>,
+    algo: A,
 }
 
 impl<F, V, R, A> Generator<F, V, R, A>
@@ -221,11 +269,11 @@ impl<F, V, R, A> Generator<F, V, R, A>
           R: Rng,
           A: Creator<F, V>,
 {
-    fn new(poisson: Builder<F, V>, rng: R) -> Self {
+    fn new(poisson: Builder<F, V>, rng: R, algo: A) -> Self {
         Generator {
             rng: rng,
             poisson: poisson,
-            _algo: PhantomData,
+            algo: algo,
         }
     }
 
@@ -246,6 +294,14 @@ impl<F, V, R, A> Generator<F, V, R, A>
     pub fn poisson_type(&self) -> Type {
         self.poisson.poisson_type
     }
+
+    /// Divides the axes into the amount of equal sized strata given for each axis, of which 0 leaves the axis continuous.
+    /// Candidates are snapped to the centers of their strata before checking that they are legal.
+    /// Only `Bridson` and `Ebeida` support stratified generation.
+    pub(crate) fn stratify(&mut self, strata: Vec<usize>) {
+        assert_eq!(V::dimension(), strata.len());
+        self.poisson.strata = strata;
+    }
 }
 
 impl<F, V, R, A> Generator<F, V, R, A>
@@ -272,8 +328,9 @@ impl<F, V, R, A> IntoIterator for Generator<F, V, R, A>
     fn into_iter(self) -> Self::IntoIter {
         PoissonIter {
             rng: self.rng,
-            algo: A::create(&self.poisson),
+            algo: self.algo.create_from(&self.poisson),
             poisson: self.poisson,
+            generated: 0,
         }
     }
 }
@@ -289,6 +346,7 @@ pub struct PoissonIter<F, V, R, A>
     poisson: Builder<F, V>,
     rng: R,
     algo: A,
+    generated: usize,
 }
 
 impl<F, V, R, A> Iterator for PoissonIter<F, V, R, A>
@@ -300,7 +358,11 @@ impl<F, V, R, A> Iterator for PoissonIter<F, V, R, A>
     type Item = V;
 
     fn next(&mut self) -> Option<Self::Item> {
-        self.algo.next(&mut self.poisson, &mut self.rng)
+        let sample = self.algo.next(&mut self.poisson, &mut self.rng);
+        if sample.is_some() {
+            self.generated += 1;
+        }
+        sample
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
@@ -324,13 +386,211 @@ impl<F, V, R, A> PoissonIter<F, V, R, A>
         self.poisson.poisson_type
     }
 
+    /// Returns the algorithm instance used for generation.
+    pub fn algorithm(&self) -> &A {
+        &self.algo
+    }
+
+    /// Returns the random number generator used for generation.
+    pub(crate) fn rng(&mut self) -> &mut R {
+        &mut self.rng
+    }
+
     /// Restricts the poisson algorithm with arbitary sample.
     pub fn restrict(&mut self, value: V) {
         self.algo.restrict(value);
     }
 
+    /// Restricts the poisson algorithm with arbitary sample that new samples can be grown from.
+    /// Only `Bridson` grows samples from others, so with other algorithms this is same as `restrict`.
+    pub fn restrict_as_seed(&mut self, value: V) {
+        self.algo.restrict_as_seed(value);
+    }
+
+    /// Restricts the poisson algorithm with arbitary sample that has its own exclusion radius.
+    /// Samples are kept at least the exclusion radius away from it, or twice the radius of the distribution if it's larger.
+    /// The sample can be removed and it conflicts with other samples like samples of the distribution do.
+    /// The sample can be outside of [0, 1]<sup>d</sup>.
+    /// Returns if the algorithm supports samples with their own radius, otherwise the sample is ignored.
+    pub fn restrict_with_radius(&mut self, value: V, radius: F) -> bool {
+        assert!(radius >= F::cast(0));
+        self.algo.restrict_with_radius(value, radius)
+    }
+
+    /// Restricts the poisson algorithm with arbitary sample handling conflicts with the distribution according to the policy.
+    /// Returns what was done together with the samples the sample conflicted with, including points restricted with their own radius.
+    pub fn try_restrict(&mut self, value: V, policy: Policy) -> Restriction<V> {
+        let conflicts = self.algo.conflicts(&self.poisson, &value);
+        let mut evicted = vec![];
+        if policy == Policy::Evict {
+            for c in &conflicts {
+                if self.algo.remove(&self.poisson, c.clone()) {
+                    evicted.push(c.clone());
+                }
+            }
+        }
+        let inserted = policy == Policy::Force || self.stays_legal(value.clone());
+        if inserted {
+            self.algo.restrict(value.clone());
+        }
+        Restriction {
+            value,
+            inserted,
+            conflicts,
+            evicted,
+        }
+    }
+
+    /// Restricts the poisson algorithm with arbitary samples in order handling conflicts according to the policy.
+    /// Samples inserted earlier are part of the distribution, so later samples conflicting with them are reported
+    /// and handled like conflicts with any other sample.
+    pub fn restrict_all<I>(&mut self, values: I, policy: Policy) -> Vec<Restriction<V>>
+        where I: IntoIterator<Item = V>
+    {
+        values.into_iter()
+            .map(|v| self.try_restrict(v, policy))
+            .collect()
+    }
+
     /// Checks legality of sample for currrent distribution.
     pub fn stays_legal(&self, value: V) -> bool {
         self.algo.stays_legal(&self.poisson, value)
     }
+
+    /// Restricts the poisson algorithm with an obstacle.
+    /// Samples are kept at least the radius away from the obstacle.
+    /// Returns if the algorithm supports obstacles, otherwise the obstacle is ignored.
+    pub fn restrict_obstacle(&mut self, obstacle: Obstacle<F, V>) -> bool {
+        let radius = self.poisson.radius;
+        self.algo.restrict_obstacle(obstacle, radius)
+    }
+
+    /// Restricts the poisson algorithm to generate only within the shape.
+    /// Unlike with obstacles, samples can be anywhere within the shape, so their disks can reach outside of it.
+    /// Returns if the algorithm supports obstacles, otherwise the domain is ignored.
+    pub fn restrict_domain(&mut self, shape: Obstacle<F, V>) -> bool {
+        self.algo.restrict_obstacle(Obstacle::Outside(Box::new(shape)), F::cast(0))
+    }
+
+    /// Removes sample from the distribution returning if it was found.
+    /// The space it freed is generated again by the following iterations.
+    pub fn remove(&mut self, value: V) -> bool {
+        self.algo.remove(&self.poisson, value)
+    }
+
+    /// Removes samples within the region from the distribution returning them.
+    /// If the generation had already finished, the following iterations generate only to the space the removed samples freed.
+    pub fn refill_region<P>(&mut self, region: P) -> Vec<V>
+        where P: Fn(&V) -> bool
+    {
+        self.algo.remove_region(&self.poisson, region)
+    }
+}
+
+impl<F, V, R, A> PoissonIter<F, V, R, A>
+    where F: Float,
+          V: Vector<F>,
+          R: Rng,
+          A: Algorithm<F, V>,
+{
+    /// Returns iterator that yields each sample together with the index of the sample it was spawned from.
+    /// Samples are indexed in the order they are yielded and seeds have no parent, so the parents form a spanning forest.
+    /// Only `Bridson` spawns samples from others, so with other algorithms every sample is a seed.
+    pub fn with_parents(self) -> WithParents<F, V, R, A> {
+        WithParents { iter: self }
+    }
+
+    /// Returns iterator that yields each sample together with its metadata and a payload.
+    /// The payload is generated when the sample is accepted by calling the closure with the sample
+    /// and the random number generator of the iterator.
+    /// Samples generated before calling this are counted in the indices, so they match the indices of parents.
+    pub fn with_payload<P, T>(self, payload: T) -> WithPayload<F, V, R, A, T>
+        where T: FnMut(&V, &mut R) -> P
+    {
+        WithPayload {
+            iter: self,
+            payload,
+        }
+    }
+}
+
+/// Iterator for generating poisson-disk distribution with the parent of each sample.
+#[derive(Clone)]
+pub struct WithParents<F, V, R, A>
+    where F: Float,
+          V: Vector<F>,
+          R: Rng,
+          A: Algorithm<F, V>,
+{
+    iter: PoissonIter<F, V, R, A>,
+}
+
+impl<F, V, R, A> Iterator for WithParents<F, V, R, A>
+    where F: Float,
+          V: Vector<F>,
+          R: Rng,
+          A: Algorithm<F, V>,
+{
+    type Item = (V, Option<usize>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|v| (v, self.iter.algo.parent()))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+/// Generated sample with its metadata.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Sample<V, P> {
+    /// Position of the sample.
+    pub value: V,
+    /// Index of the sample in the order samples were generated by the iterator.
+    pub index: usize,
+    /// Index of the sample this sample was spawned from. Only `Bridson` spawns samples from others.
+    pub parent: Option<usize>,
+    /// Level of grid subdivision at which the sample was accepted. Only `Ebeida` subdivides the grid.
+    pub level: Option<usize>,
+    /// Payload generated for the sample.
+    pub payload: P,
+}
+
+/// Iterator for generating poisson-disk distribution with metadata and payload of each sample.
+#[derive(Clone)]
+pub struct WithPayload<F, V, R, A, T>
+    where F: Float,
+          V: Vector<F>,
+          R: Rng,
+          A: Algorithm<F, V>,
+{
+    iter: PoissonIter<F, V, R, A>,
+    payload: T,
+}
+
+impl<F, V, R, A, T, P> Iterator for WithPayload<F, V, R, A, T>
+    where F: Float,
+          V: Vector<F>,
+          R: Rng,
+          A: Algorithm<F, V>,
+          T: FnMut(&V, &mut R) -> P,
+{
+    type Item = Sample<V, P>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|value| {
+            Sample {
+                index: self.iter.generated - 1,
+                parent: self.iter.algo.parent(),
+                level: self.iter.algo.level(),
+                payload: (self.payload)(&value, &mut self.iter.rng),
+                value,
+            }
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
 }
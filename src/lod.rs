@@ -0,0 +1,100 @@
+//! Module that contains generation of nested multi-resolution distributions for level of detail.
+//!
+//! Levels are generated from the coarsest to the finest so that each level restricts the generation
+//! of the next one with its samples. Because of this every level contains all the coarser levels
+//! and samples of all levels up to any level form a valid poisson-disk distribution with the radius of that level.
+//!
+//! ````rust
+//! # extern crate poisson;
+//! # extern crate rand;
+//! # extern crate nalgebra as na;
+//! # use poisson::{Type, algorithm};
+//! # use poisson::lod::Nested;
+//! # use rand::FromEntropy;
+//! # use rand::rngs::SmallRng;
+//! fn main() {
+//!     let nested = Nested::<_, na::Vector2<f64>>::new(vec![0.2, 0.1, 0.05], Type::Normal);
+//!     let samples = nested.generate(&mut SmallRng::from_entropy(), algorithm::Ebeida);
+//!     // Samples are ordered by level so the samples of the two coarsest levels form a prefix.
+//!     let medium = samples.iter().take_while(|s| s.level <= 1).count();
+//!     println!("{:?}", &samples[..medium]);
+//! }
+//! ````
+
+use {Builder, Type, Vector, Float};
+use algorithm::Creator;
+
+use rand::Rng;
+
+use num_traits::NumCast;
+
+use std::marker::PhantomData;
+
+/// Sample tagged with the coarsest level it belongs to.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Leveled<V> {
+    /// Index of the coarsest level of the sample.
+    pub level: usize,
+    /// Position of the sample.
+    pub value: V,
+}
+
+/// Generates nested poisson-disk distributions with decreasing radii.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Nested<F, V>
+    where F: Float,
+          V: Vector<F>,
+{
+    radii: Vec<F>,
+    poisson_type: Type,
+    _marker: PhantomData<V>,
+}
+
+impl<F, V> Nested<F, V>
+    where F: Float,
+          V: Vector<F>,
+{
+    /// New generator with radii of the levels from the coarsest to the finest specified.
+    /// The radii should be strictly decreasing and within ]0, √2 / 2].
+    pub fn new(radii: Vec<F>, poisson_type: Type) -> Self {
+        assert!(!radii.is_empty());
+        assert!(radii.windows(2).all(|w| w[0] > w[1]));
+        assert!(F::cast(0) < radii[radii.len() - 1]);
+        assert!(radii[0] <=
+                NumCast::from(2f64.sqrt() / 2.).expect("Casting constant should always work."));
+        Nested {
+            radii,
+            poisson_type,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns the radii of the levels from the coarsest to the finest.
+    pub fn radii(&self) -> &[F] {
+        &self.radii
+    }
+
+    /// Returns the type of the distribution.
+    pub fn poisson_type(&self) -> Type {
+        self.poisson_type
+    }
+
+    /// Generates the levels with the algorithm.
+    /// Samples are ordered by their level from the coarsest to the finest.
+    pub fn generate<R, A>(&self, rng: &mut R, algo: A) -> Vec<Leveled<V>>
+        where R: Rng,
+              A: Creator<F, V>,
+    {
+        let mut result: Vec<Leveled<V>> = vec![];
+        for (level, &radius) in self.radii.iter().enumerate() {
+            let mut iter = Builder::with_radius(radius, self.poisson_type)
+                .build(&mut *rng, algo)
+                .into_iter();
+            for s in &result {
+                iter.restrict_as_seed(s.value.clone());
+            }
+            result.extend(iter.map(|value| Leveled { level, value }));
+        }
+        result
+    }
+}
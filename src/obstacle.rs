@@ -0,0 +1,223 @@
+//! Module that contains geometric obstacles that restrict generation.
+//!
+//! Obstacles are registered to the grid cells they overlap so that checking whether a sample is legal
+//! only looks at obstacles near it. Samples are kept at least the radius of the distribution away
+//! from obstacles, so that their disks don't overlap the obstacles.
+//! Generation can also be restricted to a domain, which blocks everything outside of the domain
+//! but lets samples lie anywhere within it.
+//!
+//! ````rust
+//! # extern crate poisson;
+//! # extern crate rand;
+//! # extern crate nalgebra as na;
+//! # use poisson::{Builder, Type, algorithm};
+//! # use poisson::obstacle::Obstacle;
+//! # use rand::FromEntropy;
+//! # use rand::rngs::SmallRng;
+//! fn main() {
+//!     let mut iter = Builder::<_, na::Vector2<f64>>::with_radius(0.02, Type::Normal)
+//!         .build(SmallRng::from_entropy(), algorithm::Ebeida)
+//!         .into_iter();
+//!     // Road through the area and a building next to it.
+//!     iter.restrict_obstacle(Obstacle::Polyline(vec![
+//!         na::Vector2::new(0., 0.2),
+//!         na::Vector2::new(0.5, 0.5),
+//!         na::Vector2::new(1., 0.6),
+//!     ]));
+//!     iter.restrict_obstacle(Obstacle::Box(na::Vector2::new(0.6, 0.7), na::Vector2::new(0.8, 0.9)));
+//!     for sample in iter {
+//!         println!("{:?}", sample);
+//!     }
+//! }
+//! ````
+
+use {Type, Vector, Float};
+use utils::each_combination;
+
+/// Shape that blocks generation.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Obstacle<F, V> {
+    /// Sphere given by its center and radius.
+    Sphere(V, F),
+    /// Line segment between two points.
+    Segment(V, V),
+    /// Line going through the points in order.
+    Polyline(Vec<V>),
+    /// Axis-aligned box given by its lowest and highest corners.
+    Box(V, V),
+    /// Two dimensional polygon given by its vertices in order.
+    Polygon(Vec<V>),
+    /// Everything outside of the obstacle.
+    Outside(Box<Obstacle<F, V>>),
+}
+
+impl<F, V> Obstacle<F, V>
+    where F: Float,
+          V: Vector<F>,
+{
+    /// Returns the distance from the point to the obstacle, which is zero for points inside of it.
+    pub fn distance(&self, point: &V) -> F {
+        self.signed_distance(point).max(F::cast(0))
+    }
+
+    /// Returns the distance from the point to the boundary of the obstacle, which is negative for points inside of it.
+    /// Segments and polylines have no inside, so for them this is same as `distance`.
+    pub fn signed_distance(&self, point: &V) -> F {
+        match *self {
+            Obstacle::Sphere(ref center, radius) => (point.clone() - center.clone()).norm() - radius,
+            Obstacle::Segment(ref a, ref b) => segment_distance(point, a, b),
+            Obstacle::Polyline(ref points) => {
+                if points.len() == 1 {
+                    return (point.clone() - points[0].clone()).norm();
+                }
+                points.windows(2)
+                    .map(|w| segment_distance(point, &w[0], &w[1]))
+                    .fold(F::infinity(), |a, b| a.min(b))
+            }
+            Obstacle::Box(ref low, ref high) => {
+                let mut outside = V::zero();
+                let mut inside = -F::infinity();
+                for n in 0..V::dimension() {
+                    let d = (low[n] - point[n]).max(point[n] - high[n]);
+                    outside[n] = d.max(F::cast(0));
+                    inside = inside.max(d);
+                }
+                outside.norm() + inside.min(F::cast(0))
+            }
+            Obstacle::Polygon(ref vertices) => {
+                let edges = (0..vertices.len())
+                    .map(|i| segment_distance(point, &vertices[i], &vertices[(i + 1) % vertices.len()]))
+                    .fold(F::infinity(), |a, b| a.min(b));
+                if polygon_contains(vertices, point) {
+                    -edges
+                } else {
+                    edges
+                }
+            }
+            Obstacle::Outside(ref obstacle) => -obstacle.signed_distance(point),
+        }
+    }
+
+    /// Returns the distance from the point to the obstacle taking wrapping into account for perioditic distribution.
+    pub fn wrapped_distance(&self, point: &V, poisson_type: Type) -> F {
+        self.wrapped_signed_distance(point, poisson_type).max(F::cast(0))
+    }
+
+    /// Returns the signed distance from the point to the obstacle taking wrapping into account for perioditic distribution.
+    pub fn wrapped_signed_distance(&self, point: &V, poisson_type: Type) -> F {
+        match (self, poisson_type) {
+            (_, Type::Normal) => self.signed_distance(point),
+            // Outside of the obstacle is outside of all of its wrapped copies.
+            (Obstacle::Outside(obstacle), Type::Perioditic) => -obstacle.wrapped_signed_distance(point, poisson_type),
+            (_, Type::Perioditic) => {
+                each_combination(&[-1, 0, 1])
+                    .map(|t: V| self.signed_distance(&(point.clone() + t)))
+                    .fold(F::infinity(), |a, b| a.min(b))
+            }
+        }
+    }
+
+    /// Returns the lowest and highest corners of the axis-aligned box bounding the obstacle.
+    /// Outside of an obstacle is bounded by [0, 1]<sup>d</sup>.
+    pub fn bounds(&self) -> (V, V) {
+        match *self {
+            Obstacle::Sphere(ref center, radius) => {
+                let mut low = center.clone();
+                let mut high = center.clone();
+                for n in 0..V::dimension() {
+                    low[n] -= radius;
+                    high[n] += radius;
+                }
+                (low, high)
+            }
+            Obstacle::Segment(ref a, ref b) => point_bounds(&[a.clone(), b.clone()]),
+            Obstacle::Polyline(ref points) | Obstacle::Polygon(ref points) => point_bounds(points),
+            Obstacle::Box(ref low, ref high) => (low.clone(), high.clone()),
+            Obstacle::Outside(_) => {
+                let mut high = V::zero();
+                for n in 0..V::dimension() {
+                    high[n] = F::cast(1);
+                }
+                (V::zero(), high)
+            }
+        }
+    }
+}
+
+fn segment_distance<F, V>(point: &V, a: &V, b: &V) -> F
+    where F: Float,
+          V: Vector<F>,
+{
+    let edge = b.clone() - a.clone();
+    let offset = point.clone() - a.clone();
+    let sqlength = edge.norm_squared();
+    let t = if sqlength > F::cast(0) {
+        let dot = (0..V::dimension()).fold(F::cast(0), |sum, n| sum + edge[n] * offset[n]);
+        (dot / sqlength).max(F::cast(0)).min(F::cast(1))
+    } else {
+        F::cast(0)
+    };
+    (offset - edge * t).norm()
+}
+
+fn point_bounds<F, V>(points: &[V]) -> (V, V)
+    where F: Float,
+          V: Vector<F>,
+{
+    let mut low = points[0].clone();
+    let mut high = points[0].clone();
+    for p in &points[1..] {
+        for n in 0..V::dimension() {
+            low[n] = low[n].min(p[n]);
+            high[n] = high[n].max(p[n]);
+        }
+    }
+    (low, high)
+}
+
+/// Checks if the point is within the polygon given by its vertices in order.
+/// Counts crossings of a ray going to the positive direction of the first axis.
+pub(crate) fn polygon_contains<F, V>(vertices: &[V], point: &V) -> bool
+    where F: Float,
+          V: Vector<F>,
+{
+    let (x, y) = (point[0], point[1]);
+    let mut inside = false;
+    for (i, a) in vertices.iter().enumerate() {
+        let b = &vertices[(i + 1) % vertices.len()];
+        if (a[1] > y) != (b[1] > y) && x < a[0] + (y - a[1]) / (b[1] - a[1]) * (b[0] - a[0]) {
+            inside = !inside;
+        }
+    }
+    inside
+}
+
+#[test]
+fn distances_to_shapes_are_right() {
+    extern crate nalgebra;
+    let v = nalgebra::Vector2::new;
+    let point = v(0.5, 0.5);
+    assert!((Obstacle::Sphere(v(0.5, 0.8), 0.1).distance(&point) - 0.2f64).abs() < 1e-12);
+    assert!((Obstacle::Segment(v(0., 0.), v(1., 0.)).distance(&point) - 0.5f64).abs() < 1e-12);
+    assert!((Obstacle::Polyline(vec![v(0., 0.), v(0.2, 0.5), v(0.2, 1.)]).distance(&point) - 0.3f64).abs() < 1e-12);
+    assert!((Obstacle::Box(v(0.6, 0.9), v(0.7, 1.)).distance(&point) - 0.1f64.hypot(0.4)).abs() < 1e-12);
+    assert_eq!(0., Obstacle::Polygon(vec![v(0., 0.), v(1., 0.), v(0.5, 1.)]).distance(&point));
+    let wrapping = Obstacle::Segment(v(0.95, 0.), v(0.95, 1.));
+    assert!((wrapping.wrapped_distance(&v(0.05, 0.5), Type::Perioditic) - 0.1f64).abs() < 1e-12);
+}
+
+#[test]
+fn signed_distances_to_shapes_are_right() {
+    extern crate nalgebra;
+    let v = nalgebra::Vector2::new;
+    let point = v(0.5, 0.5);
+    assert!((Obstacle::Sphere(v(0.5, 0.6), 0.3).signed_distance(&point) + 0.2f64).abs() < 1e-12);
+    assert!((Obstacle::Box(v(0.4, 0.2), v(0.9, 0.9)).signed_distance(&point) + 0.1f64).abs() < 1e-12);
+    let polygon = Obstacle::Polygon(vec![v(0., 0.), v(1., 0.), v(1., 0.7)]);
+    assert!((polygon.signed_distance(&v(0.9, 0.1)) + 0.1f64).abs() < 1e-12);
+    let outside = Obstacle::Outside(Box::new(Obstacle::Box(v(0.4, 0.2), v(0.9, 0.9))));
+    assert!((outside.signed_distance(&point) - 0.1f64).abs() < 1e-12);
+    assert_eq!(0., outside.distance(&v(0.1, 0.5)));
+    let wrapped = Obstacle::Outside(Box::new(Obstacle::Sphere(v(0.95, 0.5), 0.1)));
+    assert!((wrapped.wrapped_signed_distance(&v(0.02, 0.5), Type::Perioditic) - 0.03f64).abs() < 1e-12);
+}
@@ -0,0 +1,165 @@
+//! Module that contains poisson point processes.
+//!
+//! Samples of these processes don't interact with each other at all
+//! which makes them useful as baseline or for sparse scattering.
+//! For the same reason wrapping of perioditic space doesn't affect them, so they don't take the type of the space.
+
+use {Vector, Float};
+
+use rand::Rng;
+use rand::distributions::{Distribution, Standard, Poisson};
+
+use std::marker::PhantomData;
+
+/// Describes intensity of the point process at given position.
+pub trait Intensity<F, V>
+    where F: Float,
+          V: Vector<F>,
+{
+    /// Returns expected amount of samples per unit volume at the position.
+    fn intensity(&self, position: &V) -> F;
+}
+
+/// Intensity that is same everywhere.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Constant<F>(pub F);
+
+impl<F, V> Intensity<F, V> for Constant<F>
+    where F: Float,
+          V: Vector<F>,
+{
+    fn intensity(&self, _: &V) -> F {
+        self.0
+    }
+}
+
+impl<F, V, T> Intensity<F, V> for T
+    where F: Float,
+          V: Vector<F>,
+          T: Fn(&V) -> F,
+{
+    fn intensity(&self, position: &V) -> F {
+        self(position)
+    }
+}
+
+/// Poisson point process in [0, 1]<sup>d</sup>.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PoissonProcess<F, V, I>
+    where F: Float,
+          V: Vector<F>,
+          I: Intensity<F, V>,
+{
+    intensity: I,
+    bound: F,
+    _marker: PhantomData<V>,
+}
+
+impl<F, V> PoissonProcess<F, V, Constant<F>>
+    where F: Float,
+          V: Vector<F>,
+{
+    /// New homogeneous poisson process with intensity specified.
+    /// The intensity should be larger than 0.
+    pub fn homogeneous(intensity: F) -> Self {
+        assert!(F::cast(0) < intensity);
+        PoissonProcess {
+            intensity: Constant(intensity),
+            bound: intensity,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<F, V, I> PoissonProcess<F, V, I>
+    where F: Float,
+          V: Vector<F>,
+          I: Intensity<F, V>,
+{
+    /// New inhomogeneous poisson process with intensity function and upper bound of the intensity specified.
+    /// The process is generated by thinning homogeneous process with the upper bound as intensity,
+    /// so the intensity function should be [0, bound] everywhere in [0, 1]<sup>d</sup>.
+    pub fn inhomogeneous(intensity: I, bound: F) -> Self {
+        assert!(F::cast(0) < bound);
+        PoissonProcess {
+            intensity,
+            bound,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns the intensity of the process at given position.
+    pub fn intensity(&self, position: &V) -> F {
+        self.intensity.intensity(position)
+    }
+
+    /// Returns the upper bound of the intensity of the process.
+    pub fn bound(&self) -> F {
+        self.bound
+    }
+
+    /// Returns iterator that generates a realisation of the process.
+    pub fn iter<R>(&self, mut rng: R) -> ProcessIter<'_, F, V, R, I>
+        where R: Rng
+    {
+        let mean = self.bound
+                       .to_f64()
+                       .expect("Bound of the intensity should be castable to f64.");
+        let remaining = rng.sample(Poisson::new(mean));
+        ProcessIter {
+            process: self,
+            rng,
+            remaining,
+        }
+    }
+
+    /// Generates a realisation of the process.
+    pub fn generate<R>(&self, rng: &mut R) -> Vec<V>
+        where R: Rng,
+              Standard: Distribution<F>,
+              Standard: Distribution<V>,
+    {
+        self.iter(rng).collect()
+    }
+}
+
+/// Iterator for generating realisation of poisson point process.
+pub struct ProcessIter<'a, F, V, R, I>
+    where F: Float + 'a,
+          V: Vector<F> + 'a,
+          R: Rng,
+          I: Intensity<F, V> + 'a,
+{
+    process: &'a PoissonProcess<F, V, I>,
+    rng: R,
+    remaining: u64,
+}
+
+impl<'a, F, V, R, I> Iterator for ProcessIter<'a, F, V, R, I>
+    where F: Float,
+          V: Vector<F>,
+          R: Rng,
+          I: Intensity<F, V>,
+          Standard: Distribution<F>,
+          Standard: Distribution<V>,
+{
+    type Item = V;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.remaining > 0 {
+            self.remaining -= 1;
+            let sample: V = self.rng.gen();
+            let intensity = self.process.intensity(&sample);
+            assert!(intensity <= self.process.bound,
+                    "Intensity of the process should never exceed its upper bound.");
+            if self.rng.gen::<F>() * self.process.bound < intensity {
+                return Some(sample);
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.remaining as usize))
+    }
+}
@@ -0,0 +1,166 @@
+//! Module that contains progressive ordering of existing distributions.
+//!
+//! Samples are ordered greedily so that each next sample is the one farthest away from the samples
+//! before it, which makes every prefix of the order as well spread as possible.
+//! The order can then be truncated at any length or radius without leaving holes.
+//!
+//! ````rust
+//! # extern crate poisson;
+//! # extern crate rand;
+//! # extern crate nalgebra as na;
+//! # use poisson::{Builder, Type, algorithm};
+//! # use poisson::progressive::order;
+//! # use rand::FromEntropy;
+//! # use rand::rngs::SmallRng;
+//! fn main() {
+//!     let samples = Builder::<_, na::Vector2<f64>>::with_radius(0.02, Type::Normal)
+//!         .build(SmallRng::from_entropy(), algorithm::Ebeida)
+//!         .generate();
+//!     let progressive = order(&samples, Type::Normal);
+//!     // Takes the longest prefix where samples are still at least 0.1 apart.
+//!     println!("{:?}", progressive.prefix(0.05));
+//! }
+//! ````
+
+use {Type, Vector, Float};
+use utils::*;
+
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+
+/// Samples ordered so that every prefix is well spread.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Progressive<F, V>
+    where F: Float,
+          V: Vector<F>,
+{
+    samples: Vec<V>,
+    radii: Vec<F>,
+}
+
+impl<F, V> Progressive<F, V>
+    where F: Float,
+          V: Vector<F>,
+{
+    /// Returns the ordered samples.
+    pub fn samples(&self) -> &[V] {
+        &self.samples
+    }
+
+    /// Returns the effective radius of each prefix so that the n:th radius is for the prefix of length n + 1.
+    /// The effective radius is half of the smallest distance between samples of the prefix.
+    /// Radius of a single sample is infinite.
+    pub fn radii(&self) -> &[F] {
+        &self.radii
+    }
+
+    /// Returns the effective radius of the prefix of given length.
+    pub fn radius(&self, length: usize) -> F {
+        assert!(0 < length && length <= self.samples.len());
+        self.radii[length - 1]
+    }
+
+    /// Returns the longest prefix which effective radius is at least the radius.
+    pub fn prefix(&self, radius: F) -> &[V] {
+        let length = self.radii.iter().take_while(|&&r| r >= radius).count();
+        &self.samples[..length]
+    }
+
+    /// Returns the ordered samples.
+    pub fn into_samples(self) -> Vec<V> {
+        self.samples
+    }
+}
+
+/// Orders samples by greedily choosing the sample farthest away from already chosen ones.
+/// The order starts from the first sample and the samples should be within [0, 1]<sup>d</sup>.
+pub fn order<F, V>(samples: &[V], poisson_type: Type) -> Progressive<F, V>
+    where F: Float,
+          V: Vector<F>,
+{
+    if samples.is_empty() {
+        return Progressive {
+            samples: vec![],
+            radii: vec![],
+        };
+    }
+    // Cells are sized so that there is around one sample per cell.
+    let dim = F::cast(V::dimension());
+    let cell = F::cast(samples.len()).powf(-F::cast(1) / dim);
+    let grid = Grid::<F, V>::new(cell * dim.sqrt() / F::cast(2), poisson_type);
+    let mut cells = vec![vec![]; grid.cells()];
+    for (i, s) in samples.iter().enumerate() {
+        let mut index = sample_to_index(s, grid.side());
+        for n in 0..V::dimension() {
+            index[n] = index[n].max(F::cast(0)).min(F::cast(grid.side() - 1));
+        }
+        let c = encode(&index, grid.side(), poisson_type).expect("Index was clamped to be within the grid.");
+        cells[c].push(i);
+    }
+
+    let mut sqdists = samples.iter()
+        .map(|s| sqdist(samples[0].clone(), s.clone(), poisson_type))
+        .collect::<Vec<_>>();
+    let mut chosen = vec![false; samples.len()];
+    chosen[0] = true;
+    let mut heap = sqdists.iter()
+        .enumerate()
+        .skip(1)
+        .map(|(index, &sqdist)| Candidate { sqdist, index })
+        .collect::<BinaryHeap<_>>();
+    let mut result = vec![samples[0].clone()];
+    let mut radii = vec![F::infinity()];
+    while let Some(Candidate { sqdist: d, index }) = heap.pop() {
+        if chosen[index] || d != sqdists[index] {
+            continue;
+        }
+        chosen[index] = true;
+        let sample = &samples[index];
+        let radius = radii[radii.len() - 1].min(d.sqrt() / F::cast(2));
+        result.push(sample.clone());
+        radii.push(radius);
+        // Only samples closer than the chosen one was to the others can get closer to the chosen ones.
+        for c in grid.cells_within(sample, d.sqrt()) {
+            for &i in &cells[c] {
+                let d = sqdist(sample.clone(), samples[i].clone(), poisson_type);
+                if !chosen[i] && d < sqdists[i] {
+                    sqdists[i] = d;
+                    heap.push(Candidate { sqdist: d, index: i });
+                }
+            }
+        }
+    }
+    Progressive {
+        samples: result,
+        radii,
+    }
+}
+
+/// Sample waiting to be chosen ordered by its squared distance to the chosen samples.
+struct Candidate<F> {
+    sqdist: F,
+    index: usize,
+}
+
+impl<F: Float> PartialEq for Candidate<F> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<F: Float> Eq for Candidate<F> {}
+
+impl<F: Float> PartialOrd for Candidate<F> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<F: Float> Ord for Candidate<F> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.sqdist
+            .partial_cmp(&other.sqdist)
+            .unwrap_or(Ordering::Equal)
+            .then_with(|| other.index.cmp(&self.index))
+    }
+}
@@ -0,0 +1,263 @@
+//! Module that contains generation of projection-aware (latinized) poisson-disk distributions.
+//!
+//! For space-filling designs samples should be well spread not only in the full space, but also
+//! when projected to each single axis and optionally to each pair of axes.
+//! Every coordinate of a new sample is drawn from the part of its axis that is still free,
+//! so the spacing of one-dimensional projections is always kept, and the sample is accepted if it
+//! also fulfills the spacing of two-dimensional projections and the full-dimensional disk condition.
+//!
+//! The full-dimensional disk condition is checked with a grid like in the other algorithms,
+//! but the amount of grid cells grows exponentially with the dimension, so beyond a limit samples are
+//! checked against each other directly, which makes this usable in any dimension.
+//!
+//! ````rust
+//! # extern crate poisson;
+//! # extern crate rand;
+//! # extern crate nalgebra as na;
+//! # use poisson::{Builder, Type};
+//! # use poisson::projection::Latinized;
+//! # use rand::FromEntropy;
+//! # use rand::rngs::SmallRng;
+//! fn main() {
+//!     // At most 51 samples fit when projections to single axes are spaced by 0.02.
+//!     let mut design = Latinized::new(
+//!         Builder::<_, na::Vector6<f64>>::with_radius(0.2, Type::Normal),
+//!         0.02);
+//!     design.set_pair_spacing(Some(0.05));
+//!     println!("{:?}", design.generate(&mut SmallRng::from_entropy()));
+//! }
+//! ````
+
+use {Builder, Type, Vector, Float};
+use utils::*;
+
+use rand::Rng;
+use rand::distributions::{Distribution, Standard};
+
+/// Maximum amount of grid cells for which the disk condition is checked with a grid.
+const MAX_CELLS: usize = 1 << 16;
+
+/// Generates poisson-disk distribution which projections to axes are also spaced.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Latinized<F, V>
+    where F: Float,
+          V: Vector<F>,
+{
+    poisson: Builder<F, V>,
+    axis_spacing: F,
+    pair_spacing: Option<F>,
+    attempts: usize,
+}
+
+impl<F, V> Latinized<F, V>
+    where F: Float,
+          V: Vector<F>,
+          Standard: Distribution<F>,
+{
+    /// New generator with the distribution and the minimum spacing of projections to single axes specified.
+    /// For n samples to fit the spacing should be less than 1 / n.
+    pub fn new(poisson: Builder<F, V>, axis_spacing: F) -> Self {
+        assert!(F::cast(0) < axis_spacing);
+        assert!(axis_spacing <= F::cast(1));
+        Latinized {
+            poisson,
+            axis_spacing,
+            pair_spacing: None,
+            attempts: 1000,
+        }
+    }
+
+    /// Sets the minimum spacing of projections to pairs of axes or disables it.
+    pub fn set_pair_spacing(&mut self, pair_spacing: Option<F>) {
+        if let Some(s) = pair_spacing {
+            assert!(F::cast(0) < s);
+            assert!(V::dimension() >= 2);
+        }
+        self.pair_spacing = pair_spacing;
+    }
+
+    /// Sets how many candidates in a row can be rejected before the generation stops.
+    pub fn set_attempts(&mut self, attempts: usize) {
+        assert!(attempts > 0);
+        self.attempts = attempts;
+    }
+
+    /// Returns the minimum spacing of projections to single axes.
+    pub fn axis_spacing(&self) -> F {
+        self.axis_spacing
+    }
+
+    /// Returns the minimum spacing of projections to pairs of axes if it's enabled.
+    pub fn pair_spacing(&self) -> Option<F> {
+        self.pair_spacing
+    }
+
+    /// Returns how many candidates in a row can be rejected before the generation stops.
+    pub fn attempts(&self) -> usize {
+        self.attempts
+    }
+
+    /// Returns the radius of the distribution.
+    pub fn radius(&self) -> F {
+        self.poisson.radius
+    }
+
+    /// Returns the type of the distribution.
+    pub fn poisson_type(&self) -> Type {
+        self.poisson.poisson_type
+    }
+
+    /// Generates the latinized poisson-disk distribution.
+    pub fn generate<R>(&self, rng: &mut R) -> Vec<V>
+        where R: Rng
+    {
+        let cells = (F::cast(V::dimension()).sqrt() / (F::cast(2) * self.poisson.radius)).powi(V::dimension() as i32);
+        let mut grid = if cells <= F::cast(MAX_CELLS) {
+            Some(Grid::new(self.poisson.radius, self.poisson.poisson_type))
+        } else {
+            None
+        };
+        let mut axes = vec![vec![]; V::dimension()];
+        let mut result: Vec<V> = vec![];
+        let mut rejected = 0;
+        while rejected < self.attempts {
+            let mut sample = V::zero();
+            for (n, axis) in axes.iter().enumerate() {
+                match self.free_coordinate(axis, rng) {
+                    Some(c) => sample[n] = c,
+                    None => return result,
+                }
+            }
+            let disk_free = match grid {
+                Some(ref grid) => is_disk_free(grid, &self.poisson, grid_index(grid, &sample), 0, sample.clone(), &[]),
+                None => is_valid(&self.poisson, &result, sample.clone()),
+            };
+            if disk_free && result.iter().all(|s| self.pairs_free(s, &sample)) {
+                if let Some(ref mut grid) = grid {
+                    let index = grid_index(grid, &sample);
+                    assert!(grid.insert(index, sample.clone(), F::cast(0)),
+                            "Index was clamped to be within the grid.");
+                }
+                for (n, axis) in axes.iter_mut().enumerate() {
+                    let index = axis.iter().position(|&c| c > sample[n]).unwrap_or(axis.len());
+                    axis.insert(index, sample[n]);
+                }
+                result.push(sample);
+                rejected = 0;
+            } else {
+                rejected += 1;
+            }
+        }
+        result
+    }
+
+    /// Draws coordinate uniformly from the part of the axis which is further than the spacing from sorted coordinates.
+    fn free_coordinate<R>(&self, axis: &[F], rng: &mut R) -> Option<F>
+        where R: Rng
+    {
+        let gaps = self.gaps(axis);
+        let total = gaps.iter().fold(F::cast(0), |a, &(_, l)| a + l);
+        if total <= F::cast(0) {
+            return None;
+        }
+        let mut position = rng.gen::<F>() * total;
+        for &(start, length) in &gaps {
+            if position < length {
+                return Some(self.wrap(start + position));
+            }
+            position -= length;
+        }
+        // Rounding errors can push the position past the end of the last gap.
+        gaps.last().map(|&(start, length)| self.wrap(start + length))
+    }
+
+    /// Moves the coordinate back to [0, 1] wrapping it around for perioditic distribution.
+    fn wrap(&self, c: F) -> F {
+        match self.poisson.poisson_type {
+            Type::Perioditic => c - c.floor(),
+            Type::Normal => c.max(F::cast(0)).min(F::cast(1)),
+        }
+    }
+
+    /// Returns starts and lengths of the free intervals of the axis.
+    fn gaps(&self, axis: &[F]) -> Vec<(F, F)> {
+        let zero = F::cast(0);
+        let one = F::cast(1);
+        let spacing = self.axis_spacing;
+        let (first, last) = match (axis.first(), axis.last()) {
+            (Some(&first), Some(&last)) => (first, last),
+            _ => return vec![(zero, one)],
+        };
+        let mut gaps = axis.windows(2)
+            .map(|w| (w[0] + spacing, w[1] - w[0] - F::cast(2) * spacing))
+            .collect::<Vec<_>>();
+        match self.poisson.poisson_type {
+            Type::Perioditic => gaps.push((last + spacing, first + one - last - F::cast(2) * spacing)),
+            Type::Normal => {
+                gaps.push((zero, first - spacing));
+                gaps.push((last + spacing, one - last - spacing));
+            }
+        }
+        gaps.retain(|&(_, l)| l > zero);
+        gaps
+    }
+
+    fn pairs_free(&self, s1: &V, s2: &V) -> bool {
+        let spacing = match self.pair_spacing {
+            Some(s) => s.powi(2),
+            None => return true,
+        };
+        let poisson_type = self.poisson.poisson_type;
+        (0..V::dimension()).all(|a| {
+            (a + 1..V::dimension()).all(|b| sqdist(project(s1, &[a, b]), project(s2, &[a, b]), poisson_type) >= spacing)
+        })
+    }
+}
+
+/// Returns the minimum distance between samples when they are projected to given axes.
+/// Returns infinity if there are less than two samples.
+pub fn projected_distance<F, V>(samples: &[V], axes: &[usize], poisson_type: Type) -> F
+    where F: Float,
+          V: Vector<F>,
+{
+    let mut result = F::infinity();
+    for (i, s1) in samples.iter().enumerate() {
+        for s2 in &samples[i + 1..] {
+            result = result.min(sqdist(project(s1, axes), project(s2, axes), poisson_type));
+        }
+    }
+    result.sqrt()
+}
+
+/// Returns index of the cell of the sample where samples on the upper edges belong to the last cells.
+fn grid_index<F, V>(grid: &Grid<F, V>, sample: &V) -> V
+    where F: Float,
+          V: Vector<F>,
+{
+    let mut index = sample_to_index(sample, grid.side());
+    for n in 0..V::dimension() {
+        index[n] = index[n].min(F::cast(grid.side() - 1));
+    }
+    index
+}
+
+/// Returns the sample with coordinates of other axes than given set to zero.
+fn project<F, V>(sample: &V, axes: &[usize]) -> V
+    where F: Float,
+          V: Vector<F>,
+{
+    let mut result = V::zero();
+    for &a in axes {
+        result[a] = sample[a];
+    }
+    result
+}
+
+#[test]
+fn coordinates_past_the_end_are_clamped_unless_perioditic() {
+    extern crate nalgebra;
+    let normal = Latinized::new(Builder::<f64, nalgebra::Vector2<_>>::with_radius(0.1, Type::Normal), 0.1);
+    assert_eq!(1., normal.wrap(1.));
+    let perioditic = Latinized::new(Builder::<f64, nalgebra::Vector2<_>>::with_radius(0.1, Type::Perioditic), 0.1);
+    assert_eq!(0., perioditic.wrap(1.));
+}
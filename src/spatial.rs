@@ -0,0 +1,153 @@
+//! Module that contains spatial index for querying generated distributions.
+//!
+//! The index buckets samples into a grid like the one the algorithms use during generation,
+//! so queries only look at the cells that can contain the answer. Distances wrap around the edges
+//! of the space for perioditic distributions.
+//!
+//! ````rust
+//! # extern crate poisson;
+//! # extern crate rand;
+//! # extern crate nalgebra as na;
+//! # use poisson::{Builder, Type, algorithm};
+//! # use poisson::spatial::SampleIndex;
+//! # use rand::FromEntropy;
+//! # use rand::rngs::SmallRng;
+//! fn main() {
+//!     let generator = Builder::<_, na::Vector2<f64>>::with_radius(0.02, Type::Perioditic)
+//!         .build(SmallRng::from_entropy(), algorithm::Ebeida);
+//!     let index = SampleIndex::from_generator(generator);
+//!     let point = na::Vector2::new(0.5, 0.5);
+//!     println!("{:?}", index.within(&point, 0.1));
+//!     println!("{:?}", index.nearest(&point, 3));
+//!     println!("{}", index.pairs(0.05).len());
+//! }
+//! ````
+
+use {Generator, Type, Vector, Float};
+use algorithm::Creator;
+use utils::*;
+
+use rand::Rng;
+
+/// Read-only spatial index over samples within [0, 1]<sup>d</sup>.
+#[derive(Clone)]
+pub struct SampleIndex<F, V>
+    where F: Float,
+          V: Vector<F>,
+{
+    samples: Vec<V>,
+    grid: Grid<F, V>,
+    cells: Vec<Vec<usize>>,
+    poisson_type: Type,
+}
+
+impl<F, V> SampleIndex<F, V>
+    where F: Float,
+          V: Vector<F>,
+{
+    /// New index over the samples with the radius of their distribution specified.
+    /// The radius decides the size of the grid cells and it doesn't have to be exact for queries to be correct.
+    pub fn new(samples: Vec<V>, radius: F, poisson_type: Type) -> Self {
+        assert!(F::cast(0) < radius);
+        let grid = Grid::<F, V>::new(radius, poisson_type);
+        assert!(grid.side() > 0);
+        let mut cells = vec![vec![]; grid.cells()];
+        for (i, s) in samples.iter().enumerate() {
+            let mut index = sample_to_index(s, grid.side());
+            for n in 0..V::dimension() {
+                index[n] = index[n].max(F::cast(0)).min(F::cast(grid.side() - 1));
+            }
+            let c = encode(&index, grid.side(), poisson_type).expect("Index was clamped to be within the grid.");
+            cells[c].push(i);
+        }
+        SampleIndex {
+            samples,
+            grid,
+            cells,
+            poisson_type,
+        }
+    }
+
+    /// New index over all of the samples the generator generates.
+    pub fn from_generator<R, A>(generator: Generator<F, V, R, A>) -> Self
+        where R: Rng,
+              A: Creator<F, V>,
+    {
+        let radius = generator.radius();
+        let poisson_type = generator.poisson_type();
+        SampleIndex::new(generator.into_iter().collect(), radius, poisson_type)
+    }
+
+    /// Returns the indexed samples.
+    pub fn samples(&self) -> &[V] {
+        &self.samples
+    }
+
+    /// Returns the type of the indexed distribution.
+    pub fn poisson_type(&self) -> Type {
+        self.poisson_type
+    }
+
+    /// Returns the distance between two points taking wrapping into account for perioditic distribution.
+    pub fn distance(&self, p1: &V, p2: &V) -> F {
+        sqdist(p1.clone(), p2.clone(), self.poisson_type).sqrt()
+    }
+
+    /// Returns indices of the samples within the distance of the point.
+    pub fn within(&self, point: &V, distance: F) -> Vec<usize> {
+        let sqdistance = distance.powi(2);
+        self.candidates(point, distance)
+            .filter(|&i| sqdist(self.samples[i].clone(), point.clone(), self.poisson_type) <= sqdistance)
+            .collect()
+    }
+
+    /// Returns indices of at most k samples nearest to the point with their distances from the nearest to the farthest.
+    pub fn nearest(&self, point: &V, k: usize) -> Vec<(usize, F)> {
+        if k == 0 {
+            return vec![];
+        }
+        let mut distance = self.grid.cell();
+        loop {
+            let mut found = self.candidates(point, distance)
+                .map(|i| (i, self.distance(&self.samples[i], point)))
+                .collect::<Vec<_>>();
+            found.sort_by(|a, b| a.1.partial_cmp(&b.1).expect("Distances shouldn't be NaN."));
+            // Samples farther than the searched distance could be closer than the last found ones.
+            if found.len() >= k && found[k - 1].1 <= distance {
+                found.truncate(k);
+                return found;
+            }
+            // All of the samples are candidates once the distance reaches across the space.
+            if distance >= F::cast(1) {
+                found.truncate(k);
+                return found;
+            }
+            distance = (distance * F::cast(2)).min(F::cast(1));
+        }
+    }
+
+    /// Returns all pairs of indices of samples within the distance of each other.
+    /// Each pair is returned once with the smaller index first.
+    pub fn pairs(&self, distance: F) -> Vec<(usize, usize)> {
+        let mut result = vec![];
+        for (i, s) in self.samples.iter().enumerate() {
+            for j in self.within(s, distance) {
+                if i < j {
+                    result.push((i, j));
+                }
+            }
+        }
+        result.sort();
+        result
+    }
+
+    /// Iterates through indices of the samples in the cells that can contain samples within the distance.
+    fn candidates<'a>(&'a self, point: &V, distance: F) -> impl Iterator<Item = usize> + 'a {
+        let cells = if distance >= F::cast(1) {
+            (0..self.cells.len()).collect()
+        } else {
+            self.grid.cells_within(point, distance)
+        };
+        cells.into_iter().flat_map(move |c| self.cells[c].iter().cloned())
+    }
+}
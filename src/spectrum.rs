@@ -0,0 +1,406 @@
+//! Module that contains spectral analysis and spectrum targeted synthesis of point sets.
+//!
+//! All of the functions work on two dimensional perioditic domain [0, 1[<sup>2</sup>.
+//!
+//! Synthesis starts from existing point set, usually poisson-disk distribution, and moves the points
+//! with gradient descent until their radially averaged power spectrum or pair correlation function
+//! matches the target.
+//!
+//! ````rust
+//! # extern crate poisson;
+//! # extern crate rand;
+//! # extern crate nalgebra as na;
+//! # use poisson::{Builder, Type, algorithm};
+//! # use poisson::spectrum::{Synthesis, Target, step_spectrum};
+//! # use rand::FromEntropy;
+//! # use rand::rngs::SmallRng;
+//! fn main() {
+//!     let samples = Builder::<_, na::Vector2<f64>>::with_samples(64, 0.5, Type::Perioditic)
+//!         .build(SmallRng::from_entropy(), algorithm::Ebeida)
+//!         .generate();
+//!     let mut synthesis = Synthesis::new(Target::Spectrum(step_spectrum(16, 6)));
+//!     synthesis.set_iterations(20);
+//!     println!("{:?}", synthesis.optimize(samples));
+//! }
+//! ````
+
+use {Vector, Float};
+
+use num_traits::NumCast;
+
+/// Describes what the synthesised point set should look like.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Target<F>
+    where F: Float,
+{
+    /// Radially averaged power spectrum where value at index i is the target
+    /// for integer frequencies which length rounds to i + 1.
+    Spectrum(Vec<F>),
+    /// Pair correlation function sampled at uniformly spaced distances on ]0, max distance]
+    /// with the max distance being the second field.
+    /// The max distance should be ]0, 0.5].
+    PairCorrelation(Vec<F>, F),
+}
+
+/// Returns step blue noise spectrum which is zero for frequencies below the cutoff and one above it.
+pub fn step_spectrum<F>(frequencies: usize, cutoff: usize) -> Vec<F>
+    where F: Float,
+{
+    (1..frequencies + 1)
+        .map(|f| if f < cutoff { F::cast(0) } else { F::cast(1) })
+        .collect()
+}
+
+/// Calculates radially averaged power spectrum of the point set for given amount of integer frequencies.
+/// Value at index i is the average of the power spectrum for frequencies which length rounds to i + 1.
+pub fn radial_spectrum<F, V>(samples: &[V], frequencies: usize) -> Vec<F>
+    where F: Float,
+          V: Vector<F>,
+{
+    assert_eq!(2, V::dimension());
+    let mut sums = vec![F::cast(0); frequencies];
+    let mut counts = vec![0; frequencies];
+    for &(ref k, bin) in &wave_vectors::<F, V>(frequencies) {
+        let (c, d) = phase_sums(samples, k);
+        sums[bin] += (c * c + d * d) / F::cast(samples.len());
+        counts[bin] += 1;
+    }
+    sums.into_iter()
+        .zip(counts)
+        .map(|(s, c)| s / F::cast(c))
+        .collect()
+}
+
+/// Estimates pair correlation function of the point set at given amount of uniformly spaced distances on ]0, max distance].
+/// The max distance should be ]0, 0.5].
+pub fn pair_correlation<F, V>(samples: &[V], bins: usize, max_distance: F) -> Vec<F>
+    where F: Float,
+          V: Vector<F>,
+{
+    assert_eq!(2, V::dimension());
+    let kernel = Kernel::new(bins, max_distance, samples.len());
+    let mut result = vec![F::cast(0); bins];
+    for (i, v1) in samples.iter().enumerate() {
+        for v2 in &samples[i + 1..] {
+            let dist = toroidal_diff(v1, v2).norm();
+            if dist > kernel.reach() {
+                continue;
+            }
+            for (b, r) in result.iter_mut().enumerate() {
+                *r += F::cast(2) * kernel.weight(b, dist);
+            }
+        }
+    }
+    for (b, r) in result.iter_mut().enumerate() {
+        *r *= kernel.normalisation(b);
+    }
+    result
+}
+
+/// Optimises point sets to match target spectrum or pair correlation function.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Synthesis<F>
+    where F: Float,
+{
+    target: Target<F>,
+    iterations: usize,
+}
+
+impl<F> Synthesis<F>
+    where F: Float,
+{
+    /// New synthesis with target specified.
+    pub fn new(target: Target<F>) -> Self {
+        if let Target::PairCorrelation(_, max_distance) = target {
+            assert!(F::cast(0) < max_distance);
+            assert!(max_distance <= NumCast::from(0.5).expect("Casting constant should always work."));
+        }
+        Synthesis {
+            target,
+            iterations: 100,
+        }
+    }
+
+    /// Sets the amount of gradient descent steps taken.
+    pub fn set_iterations(&mut self, iterations: usize) {
+        self.iterations = iterations;
+    }
+
+    /// Returns the amount of gradient descent steps taken.
+    pub fn iterations(&self) -> usize {
+        self.iterations
+    }
+
+    /// Returns the target of the synthesis.
+    pub fn target(&self) -> &Target<F> {
+        &self.target
+    }
+
+    /// Calculates the squared error between the point set and the target.
+    pub fn loss<V>(&self, samples: &[V]) -> F
+        where V: Vector<F>,
+    {
+        self.evaluate(samples, false).0
+    }
+
+    /// Moves samples so that their spectrum or pair correlation function matches the target.
+    pub fn optimize<V>(&self, samples: Vec<V>) -> Vec<V>
+        where V: Vector<F>,
+    {
+        assert_eq!(2, V::dimension());
+        if samples.len() < 2 {
+            return samples;
+        }
+        let mut samples = samples;
+        let (mut loss, mut gradient) = self.evaluate(&samples, true);
+        // Start with step that is tenth of the average spacing and adapt it based on success.
+        let mut step = F::cast(1) / (F::cast(10) * F::cast(samples.len()).sqrt());
+        for _ in 0..self.iterations {
+            let max = gradient.iter()
+                              .map(|g| g.norm())
+                              .fold(F::cast(0), |a, b| a.max(b));
+            if max == F::cast(0) {
+                break;
+            }
+            let moved = samples.iter()
+                               .zip(&gradient)
+                               .map(|(s, g)| wrap(s.clone() - g.clone() * (step / max)))
+                               .collect::<Vec<_>>();
+            let (new_loss, new_gradient) = self.evaluate(&moved, true);
+            if new_loss < loss {
+                samples = moved;
+                loss = new_loss;
+                gradient = new_gradient;
+                step *= NumCast::from(1.2).expect("Casting constant should always work.");
+            } else {
+                step /= F::cast(2);
+            }
+        }
+        samples
+    }
+
+    fn evaluate<V>(&self, samples: &[V], gradient: bool) -> (F, Vec<V>)
+        where V: Vector<F>,
+    {
+        match self.target {
+            Target::Spectrum(ref target) => spectrum_loss(samples, target, gradient),
+            Target::PairCorrelation(ref target, max_distance) => pcf_loss(samples, target, max_distance, gradient),
+        }
+    }
+}
+
+fn spectrum_loss<F, V>(samples: &[V], target: &[F], gradient: bool) -> (F, Vec<V>)
+    where F: Float,
+          V: Vector<F>,
+{
+    let n = F::cast(samples.len());
+    let waves = wave_vectors::<F, V>(target.len());
+    let sums = waves.iter()
+                    .map(|(k, _)| phase_sums(samples, k))
+                    .collect::<Vec<_>>();
+    let mut spectrum = vec![F::cast(0); target.len()];
+    let mut counts = vec![0; target.len()];
+    for (&(_, bin), &(c, d)) in waves.iter().zip(&sums) {
+        spectrum[bin] += (c * c + d * d) / n;
+        counts[bin] += 1;
+    }
+    let errors = spectrum.iter()
+                         .zip(&counts)
+                         .zip(target)
+                         .map(|((&s, &c), &t)| s / F::cast(c) - t)
+                         .collect::<Vec<_>>();
+    let loss = errors.iter().fold(F::cast(0), |a, &e| a + e * e);
+    let mut result = vec![V::zero(); if gradient { samples.len() } else { 0 }];
+    if gradient {
+        let tau = tau::<F>();
+        for (&(ref k, bin), &(c, d)) in waves.iter().zip(&sums) {
+            // dL/dP(k) * dP(k)/dθ where P(k) = (C² + D²) / n with C = Σcos θ and D = Σsin θ
+            let scale = F::cast(4) * errors[bin] / (F::cast(counts[bin]) * n) * tau;
+            for (s, g) in samples.iter().zip(&mut result) {
+                let theta = dot(k, s) * tau;
+                *g += k.clone() * (scale * (d * theta.cos() - c * theta.sin()));
+            }
+        }
+    }
+    (loss, result)
+}
+
+fn pcf_loss<F, V>(samples: &[V], target: &[F], max_distance: F, gradient: bool) -> (F, Vec<V>)
+    where F: Float,
+          V: Vector<F>,
+{
+    let pcf = pair_correlation(samples, target.len(), max_distance);
+    let errors = pcf.iter()
+                    .zip(target)
+                    .map(|(&p, &t)| p - t)
+                    .collect::<Vec<_>>();
+    let loss = errors.iter().fold(F::cast(0), |a, &e| a + e * e);
+    let mut result = vec![V::zero(); if gradient { samples.len() } else { 0 }];
+    if gradient {
+        let kernel = Kernel::new(target.len(), max_distance, samples.len());
+        let scales = errors.iter()
+                           .enumerate()
+                           .map(|(b, &e)| F::cast(4) * e * kernel.normalisation(b))
+                           .collect::<Vec<_>>();
+        for i in 0..samples.len() {
+            for j in i + 1..samples.len() {
+                let diff = toroidal_diff(&samples[i], &samples[j]);
+                let dist = diff.norm();
+                if dist == F::cast(0) || dist > kernel.reach() {
+                    continue;
+                }
+                let derivative = scales.iter()
+                                       .enumerate()
+                                       .fold(F::cast(0), |a, (b, &s)| a + s * kernel.derivative(b, dist));
+                let g = diff * (derivative / dist);
+                result[i] += g.clone();
+                result[j] -= g;
+            }
+        }
+    }
+    (loss, result)
+}
+
+/// Gaussian kernel used for estimating pair correlation function.
+struct Kernel<F>
+    where F: Float,
+{
+    width: F,
+    sigma: F,
+    samples: usize,
+    bins: usize,
+}
+
+impl<F> Kernel<F>
+    where F: Float,
+{
+    fn new(bins: usize, max_distance: F, samples: usize) -> Self {
+        let width = max_distance / F::cast(bins);
+        Kernel {
+            width,
+            sigma: width,
+            samples,
+            bins,
+        }
+    }
+
+    fn reach(&self) -> F {
+        F::cast(self.bins) * self.width + F::cast(4) * self.sigma
+    }
+
+    fn weight(&self, bin: usize, dist: F) -> F {
+        let x = (F::cast(bin + 1) * self.width - dist) / self.sigma;
+        (-x * x / F::cast(2)).exp() / (self.sigma * tau::<F>().sqrt())
+    }
+
+    fn derivative(&self, bin: usize, dist: F) -> F {
+        let x = (F::cast(bin + 1) * self.width - dist) / self.sigma;
+        self.weight(bin, dist) * x / self.sigma
+    }
+
+    fn normalisation(&self, bin: usize) -> F {
+        let pairs = F::cast(self.samples) * F::cast(self.samples.saturating_sub(1));
+        F::cast(1) / (pairs * tau::<F>() * F::cast(bin + 1) * self.width)
+    }
+}
+
+/// Returns integer wave vectors in half plane with their radial bins.
+/// Other half of the plane has same power because the spectrum of real valued signal is symmetric.
+fn wave_vectors<F, V>(frequencies: usize) -> Vec<(V, usize)>
+    where F: Float,
+          V: Vector<F>,
+{
+    let max = frequencies as isize;
+    let mut result = vec![];
+    for x in 0..max + 1 {
+        for y in -max..max + 1 {
+            if x == 0 && y <= 0 {
+                continue;
+            }
+            let len = ((x * x + y * y) as f64).sqrt().round() as usize;
+            if len >= 1 && len <= frequencies {
+                let mut k = V::zero();
+                k[0] = NumCast::from(x).expect("Casting frequency should always work.");
+                k[1] = NumCast::from(y).expect("Casting frequency should always work.");
+                result.push((k, len - 1));
+            }
+        }
+    }
+    result
+}
+
+fn dot<F, V>(v1: &V, v2: &V) -> F
+    where F: Float,
+          V: Vector<F>,
+{
+    (0..V::dimension()).fold(F::cast(0), |a, n| a + v1[n] * v2[n])
+}
+
+fn phase_sums<F, V>(samples: &[V], k: &V) -> (F, F)
+    where F: Float,
+          V: Vector<F>,
+{
+    let tau = tau::<F>();
+    samples.iter()
+           .map(|s| dot(k, s) * tau)
+           .fold((F::cast(0), F::cast(0)), |(c, d), theta| (c + theta.cos(), d + theta.sin()))
+}
+
+fn toroidal_diff<F, V>(v1: &V, v2: &V) -> V
+    where F: Float,
+          V: Vector<F>,
+{
+    let mut diff = v1.clone() - v2.clone();
+    for n in 0..V::dimension() {
+        let d = diff[n];
+        diff[n] = d - d.round();
+    }
+    diff
+}
+
+fn wrap<F, V>(mut v: V) -> V
+    where F: Float,
+          V: Vector<F>,
+{
+    for n in 0..V::dimension() {
+        v[n] = v[n] - v[n].floor();
+        if v[n] >= F::cast(1) {
+            v[n] = F::cast(0);
+        }
+    }
+    v
+}
+
+fn tau<F>() -> F
+    where F: Float,
+{
+    NumCast::from(2. * ::std::f64::consts::PI).expect("Casting constant should always work.")
+}
+
+#[test]
+fn gradients_match_finite_differences() {
+    extern crate nalgebra;
+    use rand::{Rng, SeedableRng, XorShiftRng};
+    let mut rand = XorShiftRng::from_seed([1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16]);
+    let samples = (0..20).map(|_| rand.gen::<nalgebra::Vector2<f64>>()).collect::<Vec<_>>();
+    let targets = vec![
+        Target::Spectrum(step_spectrum(8, 4)),
+        Target::PairCorrelation(vec![0.5; 10], 0.3),
+    ];
+    for target in targets {
+        let synthesis = Synthesis::new(target);
+        let (_, gradient) = synthesis.evaluate(&samples, true);
+        let h = 1e-6;
+        for (i, g) in gradient.iter().enumerate() {
+            for n in 0..2 {
+                let mut plus = samples.clone();
+                plus[i][n] += h;
+                let mut minus = samples.clone();
+                minus[i][n] -= h;
+                let numeric = (synthesis.loss(&plus) - synthesis.loss(&minus)) / (2. * h);
+                assert!((numeric - g[n]).abs() <= 1e-4 * (1. + numeric.abs()),
+                        "Gradient {} didn't match finite difference {}.", g[n], numeric);
+            }
+        }
+    }
+}
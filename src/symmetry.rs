@@ -0,0 +1,281 @@
+//! Module that contains generation of poisson-disk distributions that are invariant under a symmetry group.
+//!
+//! Samples are thrown into the fundamental domain of the group and each accepted sample is replicated
+//! through the group, so the whole distribution is mirrored, rotated or tiled according to the group.
+//!
+//! ````rust
+//! # extern crate poisson;
+//! # extern crate rand;
+//! # extern crate nalgebra as na;
+//! # use poisson::{Builder, Type};
+//! # use poisson::symmetry::{Symmetric, Symmetry};
+//! # use rand::FromEntropy;
+//! # use rand::rngs::SmallRng;
+//! fn main() {
+//!     let kaleidoscope = Symmetric::new(
+//!         Builder::<_, na::Vector2<f64>>::with_radius(0.02, Type::Normal),
+//!         Symmetry::Rotation(6));
+//!     println!("{:?}", kaleidoscope.generate(&mut SmallRng::from_entropy()));
+//! }
+//! ````
+
+use {Builder, Type, Vector, Float};
+use utils::*;
+
+use rand::Rng;
+use rand::distributions::{Distribution, Standard, Uniform};
+
+use num_traits::NumCast;
+
+/// Wallpaper groups of the square lattice.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Wallpaper {
+    /// Rotations of half turn.
+    P2,
+    /// Mirrors along the second axis.
+    Pm,
+    /// Mirrors along both axes.
+    Pmm,
+    /// Rotations of quarter turn.
+    P4,
+    /// Rotations of quarter turn and mirrors along both axes and diagonals.
+    P4m,
+}
+
+/// Symmetry group the distribution is invariant under.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Symmetry {
+    /// Mirrors across the middle of the space along given axes.
+    Mirror(Vec<usize>),
+    /// Rotations of n-fold turns around the middle of the space in the plane of the first two axes.
+    /// For perioditic space only 2 and 4-fold rotations are supported.
+    /// For non-perioditic space only samples whose all rotations stay within [0, 1]<sup>d</sup> are generated.
+    Rotation(usize),
+    /// Wallpaper group in the plane of the first two axes. Only supported for perioditic space.
+    Wallpaper(Wallpaper),
+}
+
+impl Symmetry {
+    /// Returns all images of the sample under the group including the sample itself.
+    pub fn images<F, V>(&self, sample: &V) -> Vec<V>
+        where F: Float,
+              V: Vector<F>,
+    {
+        use self::Symmetry::*;
+        use self::Wallpaper::*;
+        let one = F::cast(1);
+        let mut result = vec![sample.clone()];
+        match *self {
+            Mirror(ref axes) => {
+                for &axis in axes {
+                    for i in 0..result.len() {
+                        let mut image = result[i].clone();
+                        image[axis] = one - image[axis];
+                        result.push(image);
+                    }
+                }
+            }
+            Rotation(n) => {
+                let half = one / F::cast(2);
+                let (x, y) = (sample[0] - half, sample[1] - half);
+                let turn: F = NumCast::from(2. * ::std::f64::consts::PI / n as f64)
+                    .expect("Casting constant should always work.");
+                for k in 1..n {
+                    let (sin, cos) = (turn * F::cast(k)).sin_cos();
+                    let mut image = sample.clone();
+                    image[0] = half + x * cos - y * sin;
+                    image[1] = half + x * sin + y * cos;
+                    result.push(image);
+                }
+            }
+            Wallpaper(group) => {
+                let (x, y) = (sample[0], sample[1]);
+                let flips: &[(F, F, bool)] = match group {
+                    P2 => &[(-one, -one, false)],
+                    Pm => &[(-one, one, false)],
+                    Pmm => &[(-one, one, false), (one, -one, false), (-one, -one, false)],
+                    P4 => &[(-one, one, true), (-one, -one, false), (one, -one, true)],
+                    P4m => &[(-one, one, true), (-one, -one, false), (one, -one, true),
+                             (one, one, true), (-one, one, false), (one, -one, false), (-one, -one, true)],
+                };
+                for &(a, b, swap) in flips {
+                    let mut image = sample.clone();
+                    let (x, y) = if swap { (y, x) } else { (x, y) };
+                    image[0] = a * x;
+                    image[1] = b * y;
+                    result.push(image);
+                }
+            }
+        }
+        result
+    }
+
+    /// Checks if the sample is within the fundamental domain of the group.
+    pub fn in_fundamental<F, V>(&self, sample: &V) -> bool
+        where F: Float,
+              V: Vector<F>,
+    {
+        let half = F::cast(1) / F::cast(2);
+        match *self {
+            Symmetry::Mirror(ref axes) => axes.iter().all(|&a| sample[a] < half),
+            Symmetry::Rotation(n) => {
+                let angle = (sample[1] - half).atan2(sample[0] - half).to_f64()
+                    .expect("Angle should be castable to f64.");
+                let angle = if angle < 0. { angle + 2. * ::std::f64::consts::PI } else { angle };
+                angle < 2. * ::std::f64::consts::PI / n as f64
+            }
+            Symmetry::Wallpaper(group) => {
+                let (x, y) = (sample[0], sample[1]);
+                match group {
+                    Wallpaper::P2 | Wallpaper::Pm => x < half,
+                    Wallpaper::Pmm | Wallpaper::P4 => x < half && y < half,
+                    Wallpaper::P4m => x < half && y <= x,
+                }
+            }
+        }
+    }
+
+    /// Returns upper corner of a box that contains the fundamental domain.
+    fn bounds<F, V>(&self) -> V
+        where F: Float,
+              V: Vector<F>,
+    {
+        let half = F::cast(1) / F::cast(2);
+        let mut result = V::zero();
+        for n in 0..V::dimension() {
+            result[n] = F::cast(1);
+        }
+        match *self {
+            Symmetry::Mirror(ref axes) => {
+                for &a in axes {
+                    result[a] = half;
+                }
+            }
+            Symmetry::Rotation(_) => {}
+            Symmetry::Wallpaper(Wallpaper::P2) | Symmetry::Wallpaper(Wallpaper::Pm) => result[0] = half,
+            Symmetry::Wallpaper(_) => {
+                result[0] = half;
+                result[1] = half;
+            }
+        }
+        result
+    }
+}
+
+/// Generates poisson-disk distribution that is invariant under a symmetry group.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Symmetric<F, V>
+    where F: Float,
+          V: Vector<F>,
+{
+    poisson: Builder<F, V>,
+    symmetry: Symmetry,
+    throws: f64,
+}
+
+impl<F, V> Symmetric<F, V>
+    where F: Float,
+          V: Vector<F>,
+          Standard: Distribution<F>,
+{
+    /// New generator with the distribution and the symmetry group specified.
+    pub fn new(poisson: Builder<F, V>, symmetry: Symmetry) -> Self {
+        match symmetry {
+            Symmetry::Mirror(ref axes) => assert!(axes.iter().all(|&a| a < V::dimension())),
+            Symmetry::Rotation(n) => {
+                assert!(n > 0);
+                assert!(V::dimension() >= 2);
+                assert!(poisson.poisson_type == Type::Normal || n == 2 || n == 4);
+            }
+            Symmetry::Wallpaper(_) => {
+                assert!(V::dimension() >= 2);
+                assert_eq!(Type::Perioditic, poisson.poisson_type);
+            }
+        }
+        Symmetric {
+            poisson,
+            symmetry,
+            throws: 10.,
+        }
+    }
+
+    /// Sets the amount of samples thrown per grid cell of the fundamental domain.
+    pub fn set_throws(&mut self, throws: f64) {
+        assert!(throws > 0.);
+        self.throws = throws;
+    }
+
+    /// Returns the symmetry group of the distribution.
+    pub fn symmetry(&self) -> &Symmetry {
+        &self.symmetry
+    }
+
+    /// Returns the radius of the distribution.
+    pub fn radius(&self) -> F {
+        self.poisson.radius
+    }
+
+    /// Returns the type of the distribution.
+    pub fn poisson_type(&self) -> Type {
+        self.poisson.poisson_type
+    }
+
+    /// Generates the symmetric poisson-disk distribution.
+    pub fn generate<R>(&self, rng: &mut R) -> Vec<V>
+        where R: Rng
+    {
+        let mut grid = Grid::new(self.poisson.radius, self.poisson.poisson_type);
+        let side = F::cast(grid.side());
+        let bounds = self.symmetry.bounds::<F, V>();
+        let cells = (0..grid.cells())
+            .filter_map(|c| decode::<F, V>(c, grid.side()))
+            .filter(|c| (0..V::dimension()).all(|n| c[n] / side < bounds[n]))
+            .collect::<Vec<_>>();
+        let range = Uniform::new(0, cells.len());
+        let mut result = vec![];
+        for _ in 0..(self.throws * cells.len() as f64).ceil() as usize {
+            let mut sample = cells[rng.sample(range)].clone();
+            for n in 0..V::dimension() {
+                sample[n] = (sample[n] + rng.gen()) / side;
+            }
+            if !self.symmetry.in_fundamental(&sample) {
+                continue;
+            }
+            let images = self.images(sample);
+            if images.iter().all(|i| self.is_free(&grid, &images, i)) {
+                for image in images {
+                    let index = sample_to_index(&image, grid.side());
+                    assert!(grid.insert(index, image.clone(), F::cast(0)),
+                            "Images were checked to be within the grid.");
+                    result.push(image);
+                }
+            }
+        }
+        result
+    }
+
+    fn images(&self, sample: V) -> Vec<V> {
+        let mut images = self.symmetry.images(&sample);
+        if self.poisson.poisson_type == Type::Perioditic {
+            for image in &mut images {
+                for n in 0..V::dimension() {
+                    let c = image[n];
+                    image[n] = c - c.floor();
+                }
+            }
+        }
+        images
+    }
+
+    fn is_free(&self, grid: &Grid<F, V>, images: &[V], image: &V) -> bool {
+        let index = sample_to_index(image, grid.side());
+        if grid.get(index.clone()).is_none() {
+            return false;
+        }
+        let sqradius = (F::cast(2) * self.poisson.radius).powi(2);
+        images.iter()
+              .filter(|&i| !::std::ptr::eq(i, image))
+              .all(|i| sqdist(i.clone(), image.clone(), self.poisson.poisson_type) >= sqradius) &&
+        is_disk_free(grid, &self.poisson, index, 0, image.clone(), &[])
+    }
+}
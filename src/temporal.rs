@@ -0,0 +1,179 @@
+//! Module that contains generation of temporally coherent distributions for animation.
+//!
+//! Each frame is generated by keeping samples of the previous frame that are still valid for the new
+//! radius, domain and density, and by filling the gaps with new samples, so samples don't pop between frames.
+//! Every sample has an identity that is kept as long as the sample survives.
+//!
+//! Density is handled by keeping each sample only if a number fixed by its identity is below the density
+//! at the sample, so samples appear and disappear one by one as the density changes.
+//!
+//! ````rust
+//! # extern crate poisson;
+//! # extern crate rand;
+//! # extern crate nalgebra as na;
+//! # use poisson::{Builder, Type, algorithm};
+//! # use poisson::temporal::Frame;
+//! # use rand::FromEntropy;
+//! # use rand::rngs::SmallRng;
+//! fn main() {
+//!     let mut frame = Frame::new();
+//!     for i in 0..10 {
+//!         let radius = 0.1 - 0.005 * i as f64;
+//!         let generator = Builder::<_, na::Vector2<f64>>::with_radius(radius, Type::Normal)
+//!             .build(SmallRng::from_entropy(), algorithm::Ebeida);
+//!         frame = frame.next(generator);
+//!         println!("{:?}", frame.samples());
+//!     }
+//! }
+//! ````
+
+use {Generator, PoissonIter, Vector, Float};
+use algorithm::{Creator, Algorithm};
+use obstacle::Obstacle;
+
+use rand::Rng;
+
+/// Sample which identity stays same across frames.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Tracked<V> {
+    /// Identity of the sample.
+    pub id: u64,
+    /// Position of the sample.
+    pub value: V,
+}
+
+/// Distribution of a single frame.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Frame<V> {
+    samples: Vec<Tracked<V>>,
+    next_id: u64,
+}
+
+impl<V> Frame<V> {
+    /// New empty frame.
+    pub fn new() -> Self {
+        Frame {
+            samples: vec![],
+            next_id: 0,
+        }
+    }
+
+    /// Returns the samples of the frame.
+    pub fn samples(&self) -> &[Tracked<V>] {
+        &self.samples
+    }
+
+    /// Returns sample with given identity if it exists in the frame.
+    pub fn get(&self, id: u64) -> Option<&Tracked<V>> {
+        self.samples.iter().find(|s| s.id == id)
+    }
+
+    /// Moves every sample of the frame keeping their identities.
+    /// This can be used to follow changes of the domain such as zooming.
+    pub fn map<T>(mut self, mut transform: T) -> Self
+        where T: FnMut(&V) -> V
+    {
+        for s in &mut self.samples {
+            s.value = transform(&s.value);
+        }
+        self
+    }
+
+    /// Generates next frame with the generator in whole [0, 1]<sup>d</sup>.
+    /// Samples that were moved outside of [0, 1]<sup>d</sup> are dropped.
+    pub fn next<F, R, A>(&self, generator: Generator<F, V, R, A>) -> Self
+        where F: Float,
+              V: Vector<F>,
+              R: Rng,
+              A: Creator<F, V>,
+    {
+        self.advance(generator.into_iter(), |_| F::cast(1))
+    }
+
+    /// Generates next frame with the generator only within the domain.
+    /// Samples can be anywhere within the domain, so their disks can reach outside of it.
+    /// Samples that were moved outside of [0, 1]<sup>d</sup> or the domain are dropped.
+    /// If the algorithm of the generator doesn't support obstacles, samples are generated everywhere and
+    /// the ones outside of the domain are dropped, so the domain isn't covered up to its boundary.
+    pub fn next_within<F, R, A>(&self, generator: Generator<F, V, R, A>, domain: Obstacle<F, V>) -> Self
+        where F: Float,
+              V: Vector<F>,
+              R: Rng,
+              A: Creator<F, V>,
+    {
+        let mut iter = generator.into_iter();
+        if iter.restrict_domain(domain.clone()) {
+            self.advance(iter, |_| F::cast(1))
+        } else {
+            self.advance(iter, |v| if domain.distance(v) > F::cast(0) { F::cast(0) } else { F::cast(1) })
+        }
+    }
+
+    /// Generates next frame with the generator in whole [0, 1]<sup>d</sup> thinned by the density.
+    /// Density gives the fraction of samples that are kept at each point and should be within [0, 1].
+    /// Samples that were moved outside of [0, 1]<sup>d</sup> are dropped.
+    pub fn next_with_density<F, R, A, D>(&self, generator: Generator<F, V, R, A>, density: D) -> Self
+        where F: Float,
+              V: Vector<F>,
+              R: Rng,
+              A: Creator<F, V>,
+              D: Fn(&V) -> F,
+    {
+        self.advance(generator.into_iter(), density)
+    }
+
+    /// Samples of this frame are kept from oldest to newest as long as they are legal for the generator,
+    /// don't conflict with already kept samples and are ranked below the density. Gaps are then filled by the generator.
+    fn advance<F, R, A, D>(&self, mut iter: PoissonIter<F, V, R, A>, density: D) -> Self
+        where F: Float,
+              V: Vector<F>,
+              R: Rng,
+              A: Algorithm<F, V>,
+              D: Fn(&V) -> F,
+    {
+        let mut previous = self.samples.iter().collect::<Vec<_>>();
+        previous.sort_by_key(|s| s.id);
+        let mut samples = vec![];
+        for s in previous {
+            let inside = (0..V::dimension()).all(|n| F::cast(0) <= s.value[n] && s.value[n] <= F::cast(1));
+            if inside && rank::<F>(s.id) < density(&s.value) && iter.stays_legal(s.value.clone()) {
+                iter.restrict(s.value.clone());
+                samples.push(s.clone());
+            }
+        }
+        let mut next_id = self.next_id;
+        for value in iter {
+            // Dropped samples still block their disks, so the kept ones stay a thinned poisson-disk distribution.
+            if rank::<F>(next_id) < density(&value) {
+                samples.push(Tracked {
+                    id: next_id,
+                    value,
+                });
+            }
+            next_id += 1;
+        }
+        Frame {
+            samples,
+            next_id,
+        }
+    }
+}
+
+/// Returns a number within [0, 1[ that is fixed by the identity, but spread evenly over identities.
+fn rank<F>(id: u64) -> F
+    where F: Float,
+{
+    // Mixing function of SplitMix64.
+    let mut z = id.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^= z >> 31;
+    let rank = (z >> 11) as f64 / (1u64 << 53) as f64;
+    ::num_traits::NumCast::from(rank).expect("Rank should be castable to float.")
+}
+
+impl<V> Default for Frame<V> {
+    fn default() -> Self {
+        Frame::new()
+    }
+}
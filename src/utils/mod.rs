@@ -1,6 +1,7 @@
 //! Helper functions that poisson uses.
 
 use {Builder, Type, Vector, Float};
+use obstacle::Obstacle;
 
 use num_traits::NumCast;
 
@@ -18,10 +19,13 @@ pub struct Grid<F, V>
     where F: Float,
           V: Vector<F>,
 {
-    data: Vec<Vec<V>>,
+    data: Vec<Vec<(V, F)>>,
+    largest: F,
     side: usize,
     cell: F,
     poisson_type: Type,
+    obstacles: Vec<(Obstacle<F, V>, F)>,
+    blocked: Vec<Vec<usize>>,
     _marker: PhantomData<F>,
 }
 
@@ -39,17 +43,35 @@ impl<F, V> Grid<F, V>
             cell: cell,
             side: side,
             data: vec![vec![]; side.pow(dim.to_u32().expect("Dimension should be always be castable to u32."))],
+            largest: F::cast(0),
             poisson_type: poisson_type,
+            obstacles: vec![],
+            blocked: vec![],
             _marker: PhantomData,
         }
     }
 
-    pub fn get(&self, index: V) -> Option<&Vec<V>> {
+    /// Returns the points in the cell together with the distances they keep samples away.
+    pub fn get(&self, index: V) -> Option<&Vec<(V, F)>> {
         encode(&index, self.side, self.poisson_type).map(|t| &self.data[t])
     }
 
-    pub fn get_mut(&mut self, index: V) -> Option<&mut Vec<V>> {
-        encode(&index, self.side, self.poisson_type).map(move |t| &mut self.data[t])
+    /// Inserts the point to the cell together with the distance it keeps samples away returning if the cell exists.
+    /// Samples of the distribution keep the distance of the distribution, so they are inserted with zero.
+    pub fn insert(&mut self, index: V, sample: V, distance: F) -> bool {
+        match encode(&index, self.side, self.poisson_type) {
+            Some(t) => {
+                self.data[t].push((sample, distance));
+                self.largest = self.largest.max(distance);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Returns the largest distance that points in the grid keep samples away.
+    pub fn largest(&self) -> F {
+        self.largest
     }
 
     pub fn cells(&self) -> usize {
@@ -63,6 +85,222 @@ impl<F, V> Grid<F, V>
     pub fn cell(&self) -> F {
         self.cell
     }
+
+    /// Returns encoded indices of the cells that can contain samples within given distance of the sample.
+    pub fn cells_within(&self, sample: &V, distance: F) -> Vec<usize> {
+        let index = sample_to_index(sample, self.side);
+        let reach = (distance * F::cast(self.side)).ceil();
+        // Cells further than this can't be reached, so the whole grid is covered and combinations needn't be enumerated.
+        let covering = match self.poisson_type {
+            Type::Normal => self.side - 1,
+            Type::Perioditic => self.side / 2,
+        };
+        if reach >= F::cast(covering) {
+            return (0..self.data.len()).collect();
+        }
+        let reach = reach.to_isize()
+                        .expect("Expected that reach of the distance would be castable to isize.");
+        let choices = (-reach..reach + 1).collect::<Vec<_>>();
+        let mut cells = each_combination::<F, _, V>(&choices)
+                            .filter_map(|t| encode(&(index.clone() + t), self.side, self.poisson_type))
+                            .collect::<Vec<_>>();
+        // With perioditic grid the same cell can be reached from multiple directions.
+        cells.sort();
+        cells.dedup();
+        cells
+    }
+
+    /// Iterates through points in the cells that can contain points within given distance of the sample.
+    pub fn neighbours<'a>(&'a self, sample: &V, distance: F) -> impl Iterator<Item = &'a (V, F)> + 'a {
+        self.cells_within(sample, distance)
+            .into_iter()
+            .flat_map(move |c| self.data[c].iter())
+    }
+
+    /// Removes the sample from the cell it belongs to returning the distance it kept samples away if it was found.
+    pub fn remove(&mut self, sample: &V) -> Option<F> {
+        let index = sample_to_index(sample, self.side);
+        if let Some(t) = encode(&index, self.side, self.poisson_type) {
+            let cell = &mut self.data[t];
+            if let Some(i) = cell.iter().position(|v| same(&v.0, sample)) {
+                return Some(cell.swap_remove(i).1);
+            }
+        }
+        None
+    }
+
+    /// Registers the obstacle to the cells that contain points closer to it than the distance.
+    pub fn obstruct(&mut self, obstacle: Obstacle<F, V>, distance: F) {
+        assert_valid(&obstacle);
+        if self.blocked.is_empty() {
+            self.blocked = vec![vec![]; self.data.len()];
+        }
+        let (bottom, top) = obstacle.bounds();
+        let (mut low, mut high) = (bottom.clone(), top);
+        let last = F::cast(self.side - 1);
+        for n in 0..V::dimension() {
+            low[n] = ((bottom[n] - distance) * F::cast(self.side)).floor();
+            high[n] = ((high[n] + distance) * F::cast(self.side)).floor();
+            match self.poisson_type {
+                Type::Normal => {
+                    low[n] = low[n].max(F::cast(0));
+                    high[n] = high[n].min(last);
+                    if low[n] > high[n] {
+                        return;
+                    }
+                }
+                Type::Perioditic => {
+                    // Cells wrap around, so going through every cell once is enough.
+                    if high[n] - low[n] >= last {
+                        low[n] = F::cast(0);
+                        high[n] = last;
+                    }
+                }
+            }
+        }
+        let id = self.obstacles.len();
+        self.obstacles.push((obstacle, distance));
+        let mut index = low.clone();
+        loop {
+            if let Some(c) = encode(&index, self.side, self.poisson_type) {
+                self.blocked[c].push(id);
+            }
+            let mut n = 0;
+            loop {
+                if n == V::dimension() {
+                    return;
+                }
+                index[n] += F::cast(1);
+                if index[n] <= high[n] {
+                    break;
+                }
+                index[n] = low[n];
+                n += 1;
+            }
+        }
+    }
+
+    /// Checks that the sample isn't closer to any obstacle than the distance it was registered with.
+    pub fn is_clear(&self, sample: &V) -> bool {
+        if self.blocked.is_empty() {
+            return true;
+        }
+        let mut index = sample_to_index(sample, self.side);
+        if self.poisson_type == Type::Normal {
+            for n in 0..V::dimension() {
+                index[n] = index[n].max(F::cast(0)).min(F::cast(self.side - 1));
+            }
+        }
+        let cell = encode(&index, self.side, self.poisson_type).expect("Index was clamped to be within the grid.");
+        self.blocked[cell].iter().all(|&i| {
+            let (ref obstacle, distance) = self.obstacles[i];
+            obstacle.wrapped_signed_distance(sample, self.poisson_type) >= distance
+        })
+    }
+
+    /// Checks if the whole cell is closer to a single obstacle than the distance it was registered with.
+    pub fn cell_blocked(&self, index: &V, spacing: F) -> bool {
+        if self.blocked.is_empty() {
+            return false;
+        }
+        let mut center = index.clone();
+        for n in 0..V::dimension() {
+            center[n] = (center[n] + F::cast(1) / F::cast(2)) * spacing;
+        }
+        let half_diagonal = spacing * F::cast(V::dimension()).sqrt() / F::cast(2);
+        let mut cell = sample_to_index(&center, self.side);
+        if self.poisson_type == Type::Normal {
+            for n in 0..V::dimension() {
+                cell[n] = cell[n].max(F::cast(0)).min(F::cast(self.side - 1));
+            }
+        }
+        let cell = encode(&cell, self.side, self.poisson_type).expect("Index was clamped to be within the grid.");
+        // Distance to an obstacle changes at most as much as the point moves, so the center is enough to check.
+        self.blocked[cell].iter().any(|&i| {
+            let (ref obstacle, distance) = self.obstacles[i];
+            obstacle.wrapped_signed_distance(&center, self.poisson_type) + half_diagonal < distance
+        })
+    }
+
+    /// Removes all samples that match the predicate returning them with the distances they kept samples away.
+    pub fn remove_where<P>(&mut self, mut predicate: P) -> Vec<(V, F)>
+        where P: FnMut(&V) -> bool
+    {
+        let mut removed = vec![];
+        for cell in &mut self.data {
+            let mut i = 0;
+            while i < cell.len() {
+                if predicate(&cell[i].0) {
+                    removed.push(cell.swap_remove(i));
+                } else {
+                    i += 1;
+                }
+            }
+        }
+        removed
+    }
+}
+
+fn assert_valid<F, V>(obstacle: &Obstacle<F, V>)
+    where F: Float,
+          V: Vector<F>,
+{
+    match *obstacle {
+        Obstacle::Sphere(_, radius) => assert!(radius >= F::cast(0)),
+        Obstacle::Polyline(ref points) => assert!(!points.is_empty()),
+        Obstacle::Box(ref low, ref high) => assert!((0..V::dimension()).all(|n| low[n] <= high[n])),
+        Obstacle::Polygon(ref vertices) => {
+            assert_eq!(2, V::dimension());
+            assert!(vertices.len() >= 3);
+        }
+        Obstacle::Segment(..) => {}
+        Obstacle::Outside(ref obstacle) => assert_valid(obstacle),
+    }
+}
+
+#[test]
+fn neighbouring_cells_are_not_repeated_when_wrapping() {
+    extern crate nalgebra;
+    let grid = Grid::<f64, nalgebra::Vector2<_>>::new(0.3, Type::Perioditic);
+    let cells = grid.cells_within(&nalgebra::Vector2::new(0.5, 0.5), 0.9);
+    assert_eq!(grid.cells(), cells.len());
+}
+
+#[test]
+fn reaching_over_the_grid_covers_every_cell() {
+    extern crate nalgebra;
+    for &t in &[Type::Normal, Type::Perioditic] {
+        let grid = Grid::<f64, nalgebra::Vector4<_>>::new(0.05, t);
+        let cells = grid.cells_within(&nalgebra::Vector4::new(0.1, 0.2, 0.3, 0.4), ::std::f64::INFINITY);
+        assert_eq!((0..grid.cells()).collect::<Vec<_>>(), cells);
+    }
+}
+
+#[test]
+fn removing_from_grid_works() {
+    extern crate nalgebra;
+    let mut grid = Grid::<f64, nalgebra::Vector2<_>>::new(0.1, Type::Normal);
+    let sample = nalgebra::Vector2::new(0.25, 0.75);
+    assert!(grid.insert(sample_to_index(&sample, grid.side()), sample, 0.3));
+    assert_eq!(0.3, grid.largest());
+    assert_eq!(1, grid.neighbours(&sample, 0.2).count());
+    assert_eq!(Some(0.3), grid.remove(&sample));
+    assert_eq!(None, grid.remove(&sample));
+    assert_eq!(0, grid.neighbours(&sample, 0.2).count());
+}
+
+#[test]
+fn removing_from_grid_by_predicate_works() {
+    extern crate nalgebra;
+    let mut grid = Grid::<f64, nalgebra::Vector2<_>>::new(0.1, Type::Normal);
+    for &(x, y) in &[(0.1, 0.1), (0.15, 0.12), (0.8, 0.8)] {
+        let sample = nalgebra::Vector2::new(x, y);
+        grid.insert(sample_to_index(&sample, grid.side()), sample, 0.);
+    }
+    let removed = grid.remove_where(|v| v.x < 0.5);
+    assert_eq!(2, removed.len());
+    assert_eq!(0, grid.neighbours(&nalgebra::Vector2::new(0.1, 0.1), 0.2).count());
+    assert_eq!(1, grid.neighbours(&nalgebra::Vector2::new(0.8, 0.8), 0.2).count());
 }
 
 pub fn encode<F, V>(v: &V, side: usize, poisson_type: Type) -> Option<usize>
@@ -198,19 +436,223 @@ pub fn is_disk_free<F, V>(grid: &Grid<F, V>,
                           index: V,
                           level: usize,
                           sample: V,
-                          outside: &[V])
+                          outside: &[(V, F)])
                           -> bool
     where F: Float,
           V: Vector<F>,
 {
     let parent = get_parent(index, level);
-    let sqradius = (F::cast(2) * poisson.radius).powi(2);
+    let distance = F::cast(2) * poisson.radius;
+    let free = |p: &(V, F)| is_far(p, &sample, distance, poisson.poisson_type);
     // NOTE: This does unnessary checks for corners, but it doesn't affect much in higher dimensions: 5^d vs 5^d - 2d
     each_combination(&[-2, -1, 0, 1, 2])
         .filter_map(|t| grid.get(parent.clone() + t))
         .flat_map(|t| t)
-        .all(|v| sqdist(v.clone(), sample.clone(), poisson.poisson_type) >= sqradius) &&
-    is_valid(poisson, outside, sample)
+        .all(&free) &&
+    // Points that keep samples further away can be outside of the nearest cells.
+    (grid.largest() <= distance || grid.neighbours(&sample, grid.largest()).all(&free)) &&
+    grid.is_clear(&sample) &&
+    outside.iter().all(&free)
+}
+
+/// Returns the points in the grid and outside of it that are too close to the sample.
+pub fn conflicts<F, V>(grid: &Grid<F, V>, poisson: &Builder<F, V>, outside: &[(V, F)], sample: &V) -> Vec<V>
+    where F: Float,
+          V: Vector<F>,
+{
+    let distance = F::cast(2) * poisson.radius;
+    grid.neighbours(sample, distance.max(grid.largest()))
+        .chain(outside)
+        .filter(|p| !is_far(p, sample, distance, poisson.poisson_type))
+        .map(|p| p.0.clone())
+        .collect()
+}
+
+/// Checks if the sample is at least the distance away from the point,
+/// or the distance the point keeps samples away if it's larger.
+pub fn is_far<F, V>(point: &(V, F), sample: &V, distance: F, poisson_type: Type) -> bool
+    where F: Float,
+          V: Vector<F>,
+{
+    sqdist(point.0.clone(), sample.clone(), poisson_type) >= distance.max(point.1).powi(2)
+}
+
+/// Checks if the whole cell is within given distance of a single point in the grid,
+/// or within the distance the point keeps samples away if it's larger.
+pub fn cell_within<F, V>(grid: &Grid<F, V>, poisson_type: Type, index: &V, spacing: F, distance: F) -> bool
+    where F: Float,
+          V: Vector<F>,
+{
+    let corners = each_combination(&[0, 1])
+                      .map(|t: V| (index.clone() + t) * spacing)
+                      .collect::<Vec<_>>();
+    let mut center = index.clone();
+    for n in 0..V::dimension() {
+        center[n] = (center[n] + F::cast(1) / F::cast(2)) * spacing;
+    }
+    let half_diagonal = spacing * F::cast(V::dimension()).sqrt() / F::cast(2);
+    corners_within(grid, poisson_type, &corners, &center, half_diagonal, distance)
+}
+
+/// Checks if every point of the cell snapped to the strata of the distribution is within given distance of a single point in the grid,
+/// or within the distance the point keeps samples away if it's larger.
+/// Without strata this is same as `cell_within`.
+pub fn snapped_cell_within<F, V>(grid: &Grid<F, V>, poisson: &Builder<F, V>, index: &V, spacing: F, distance: F) -> bool
+    where F: Float,
+          V: Vector<F>,
+{
+    if poisson.strata.is_empty() {
+        return cell_within(grid, poisson.poisson_type, index, spacing, distance);
+    }
+    snapped_parts(poisson, index, spacing).iter().all(|corners| {
+        let (mut low, mut high) = (corners[0].clone(), corners[0].clone());
+        for c in corners {
+            for n in 0..V::dimension() {
+                low[n] = low[n].min(c[n]);
+                high[n] = high[n].max(c[n]);
+            }
+        }
+        let center = (low.clone() + high.clone()) * (F::cast(1) / F::cast(2));
+        let half_diagonal = (high - low).norm() / F::cast(2);
+        corners_within(grid, poisson.poisson_type, corners, &center, half_diagonal, distance)
+    })
+}
+
+fn corners_within<F, V>(grid: &Grid<F, V>, poisson_type: Type, corners: &[V], center: &V, half_diagonal: F, distance: F) -> bool
+    where F: Float,
+          V: Vector<F>,
+{
+    // Disks are convex so if they contain all corners of the cell they contain the whole cell.
+    grid.neighbours(center, distance.max(grid.largest()) + half_diagonal)
+        .any(|&(ref s, own)| {
+            let sqdistance = distance.max(own).powi(2);
+            corners.iter().all(|c| sqdist(s.clone(), c.clone(), poisson_type) <= sqdistance)
+        })
+}
+
+/// Returns the parts that the points of the cell are snapped to, each given by its corners.
+/// Cells spanning two strata of an axis are split to a part for each of them, so the parts can be covered by different disks.
+/// Cells spanning more strata of an axis are snapped to a part containing all of them, which is split as the cell is subdivided.
+pub fn snapped_parts<F, V>(poisson: &Builder<F, V>, index: &V, spacing: F) -> Vec<Vec<V>>
+    where F: Float,
+          V: Vector<F>,
+{
+    let mut parts = vec![vec![V::zero()]];
+    for n in 0..V::dimension() {
+        let low = index[n] * spacing;
+        let high = (index[n] + F::cast(1)) * spacing;
+        // Coordinates of the corners of each part along the axis.
+        let choices = match poisson.strata.get(n) {
+            Some(&levels) if levels > 0 => {
+                // Cells are half-open, so their upper corner belongs to the stratum below it.
+                let (first, last) = (stratum(low, levels, false), stratum(high, levels, true));
+                let (center_first, center_last) = (stratum_center(first, levels), stratum_center(last, levels));
+                if first == last {
+                    vec![vec![center_first]]
+                } else if first + 1 == last {
+                    vec![vec![center_first], vec![center_last]]
+                } else {
+                    vec![vec![center_first, center_last]]
+                }
+            }
+            _ => vec![vec![low, high]],
+        };
+        parts = parts.iter()
+            .flat_map(|corners| {
+                choices.iter().map(move |coordinates| {
+                    corners.iter()
+                        .flat_map(|c| {
+                            coordinates.iter().map(move |&x| {
+                                let mut c = c.clone();
+                                c[n] = x;
+                                c
+                            })
+                        })
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+    }
+    parts
+}
+
+/// Moves the sample to the centers of the strata of the distribution along the stratified axes.
+pub fn snap<F, V>(poisson: &Builder<F, V>, sample: V) -> V
+    where F: Float,
+          V: Vector<F>,
+{
+    snap_to_strata(&poisson.strata, sample)
+}
+
+/// Moves the sample to the centers of the strata along the axes that have them.
+/// Axes with 0 strata and the axes beyond the given strata are left as they are.
+pub fn snap_to_strata<F, V>(strata: &[usize], mut sample: V) -> V
+    where F: Float,
+          V: Vector<F>,
+{
+    for (n, &levels) in strata.iter().enumerate() {
+        if levels > 0 {
+            sample[n] = stratum_center(stratum(sample[n], levels, false), levels);
+        }
+    }
+    sample
+}
+
+/// Returns the index of the base level cell of the sample along the stratified axes,
+/// as snapped samples can be moved out of the cell they were generated to.
+pub fn snapped_index<F, V>(poisson: &Builder<F, V>, mut index: V, sample: &V, side: usize) -> V
+    where F: Float,
+          V: Vector<F>,
+{
+    for (n, &levels) in poisson.strata.iter().enumerate() {
+        if levels > 0 {
+            index[n] = (sample[n] * F::cast(side)).floor();
+        }
+    }
+    index
+}
+
+/// Returns the stratum that the coordinate is in.
+/// Coordinates on the border of two strata belong to the upper one, unless the coordinate is an upper bound.
+fn stratum<F>(coordinate: F, levels: usize, upper: bool) -> usize
+    where F: Float,
+{
+    let scaled = coordinate.max(F::cast(0)).min(F::cast(1)) * F::cast(levels);
+    let nearest = scaled.round();
+    // Borders are compared with tolerance, so rounding errors don't move coordinates to neighbouring strata.
+    let stratum = if (scaled - nearest).abs() <= F::epsilon() * F::cast(4) * nearest.max(F::cast(1)) {
+        if upper && nearest > F::cast(0) {
+            nearest - F::cast(1)
+        } else {
+            nearest
+        }
+    } else {
+        scaled.floor()
+    };
+    stratum.to_usize()
+        .expect("Stratum of coordinate within [0, 1] should be castable to usize.")
+        .min(levels - 1)
+}
+
+fn stratum_center<F>(stratum: usize, levels: usize) -> F
+    where F: Float,
+{
+    (F::cast(stratum) + F::cast(1) / F::cast(2)) / F::cast(levels)
+}
+
+#[test]
+fn cell_within_needs_single_disk() {
+    extern crate nalgebra;
+    let mut grid = Grid::<f64, nalgebra::Vector2<_>>::new(0.1, Type::Normal);
+    let index = nalgebra::Vector2::new(4., 4.);
+    let spacing = 0.1;
+    for &(x, y) in &[(0.4, 0.4), (0.5, 0.4), (0.4, 0.5), (0.5, 0.5)] {
+        let sample = nalgebra::Vector2::new(x, y);
+        grid.insert(sample_to_index(&sample, grid.side()), sample, 0.);
+    }
+    // Every corner is covered by some disk, but the center of the cell isn't.
+    assert!(!cell_within(&grid, Type::Normal, &index, spacing, 0.06));
+    assert!(cell_within(&grid, Type::Normal, &index, spacing, 0.15));
 }
 
 pub fn is_valid<F, V>(poisson: &Builder<F, V>, samples: &[V], sample: V) -> bool
@@ -230,14 +672,25 @@ pub fn sqdist<F, V>(v1: V, v2: V, poisson_type: Type) -> F
     use Type::*;
     let diff = v2 - v1;
     match poisson_type {
-        Perioditic =>
-            each_combination(&[-1, 0, 1])
-                .map(|v| (diff.clone() + v).norm_squared())
-                .fold(F::max_value(), |a, b| a.min(b)),
+        // Wrapping each axis separately finds the same closest copy as trying every combination of wraps.
+        Perioditic => {
+            (0..V::dimension()).fold(F::cast(0), |d, n| {
+                let c = diff[n].abs();
+                d + c.min((c - F::cast(1)).abs()).powi(2)
+            })
+        }
         Normal => diff.norm_squared(),
     }
 }
 
+/// Checks if two vectors have exactly the same scalars.
+pub fn same<F, V>(v1: &V, v2: &V) -> bool
+    where F: Float,
+          V: Vector<F>,
+{
+    (0..V::dimension()).all(|n| v1[n] == v2[n])
+}
+
 pub fn get_parent<F, V>(mut index: V, level: usize) -> V
     where F: Float,
           V: Vector<F>,
@@ -0,0 +1,189 @@
+//! Module that contains generation of well-spaced point sets for meshing.
+//!
+//! Well-spaced point set has both minimum distance between samples and maximum gap:
+//! every point of the space is within the maximum gap of some sample.
+//! Poisson-disk distribution guarantees the minimum distance and maximal one guarantees
+//! the maximum gap to be the minimum distance, which is the smallest possible bound.
+//!
+//! ````rust
+//! # extern crate poisson;
+//! # extern crate rand;
+//! # extern crate nalgebra as na;
+//! # use poisson::{Builder, Type, algorithm};
+//! # use poisson::well_spaced::WellSpaced;
+//! # use rand::FromEntropy;
+//! # use rand::rngs::SmallRng;
+//! fn main() {
+//!     let generator = Builder::<_, na::Vector2<f64>>::with_radius(0.05, Type::Normal)
+//!         .build(SmallRng::from_entropy(), algorithm::Bridson);
+//!     // Every point should be within 1.5 times the minimum distance of some sample.
+//!     let (samples, coverage) = WellSpaced::new(0.15).generate(generator);
+//!     assert!(coverage.met);
+//!     println!("{:?}", samples);
+//! }
+//! ````
+
+use {Generator, Type, Vector, Float};
+use algorithm::Creator;
+use utils::*;
+
+use rand::Rng;
+
+/// Result of checking the maximum gap of a point set.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Coverage {
+    /// Whether every point of the space was verified to be within the maximum gap of some sample.
+    pub met: bool,
+    /// Amount of smallest checked cells that couldn't be verified to be covered.
+    pub uncovered: usize,
+}
+
+/// Generates point sets with both minimum distance and maximum gap.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct WellSpaced<F>
+    where F: Float,
+{
+    max_gap: F,
+    depth: usize,
+}
+
+impl<F> WellSpaced<F>
+    where F: Float,
+{
+    /// New generator with maximum gap specified.
+    /// For the guarantee to be achievable the maximum gap should be at least the minimum distance
+    /// which is two times the radius of the generator.
+    pub fn new(max_gap: F) -> Self {
+        assert!(F::cast(0) < max_gap);
+        WellSpaced {
+            max_gap,
+            depth: 8,
+        }
+    }
+
+    /// Sets how many times grid cells are subdivided while checking for coverage.
+    pub fn set_depth(&mut self, depth: usize) {
+        self.depth = depth;
+    }
+
+    /// Returns the maximum gap.
+    pub fn max_gap(&self) -> F {
+        self.max_gap
+    }
+
+    /// Returns how many times grid cells are subdivided while checking for coverage.
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Generates samples with the generator and fills the gaps that are larger than the maximum gap.
+    /// Returns the samples and whether the maximum gap was verified.
+    /// The closer the maximum gap is to the minimum distance the deeper cells need to be subdivided
+    /// for the verification to succeed, so at exactly the minimum distance it usually isn't met.
+    pub fn generate<V, R, A>(&self, generator: Generator<F, V, R, A>) -> (Vec<V>, Coverage)
+        where V: Vector<F>,
+              R: Rng,
+              A: Creator<F, V>,
+    {
+        assert!(F::cast(2) * generator.radius() <= self.max_gap);
+        let poisson_type = generator.poisson_type();
+        let mut iter = generator.into_iter();
+        let mut grid = Grid::new(iter.radius(), poisson_type);
+        let mut samples = vec![];
+        for sample in iter.by_ref() {
+            insert(&mut grid, sample.clone(), poisson_type);
+            samples.push(sample);
+        }
+        loop {
+            let uncovered = self.uncovered(&grid, poisson_type);
+            if uncovered.is_empty() {
+                return (samples, Coverage { met: true, uncovered: 0 });
+            }
+            let mut filled = false;
+            for center in &uncovered {
+                if iter.stays_legal(center.clone()) {
+                    iter.restrict(center.clone());
+                    insert(&mut grid, center.clone(), poisson_type);
+                    samples.push(center.clone());
+                    filled = true;
+                }
+            }
+            if !filled {
+                return (samples, Coverage { met: false, uncovered: uncovered.len() });
+            }
+        }
+    }
+
+    /// Checks if every point of the space is within the maximum gap of some sample.
+    pub fn check<V>(&self, samples: &[V], poisson_type: Type) -> Coverage
+        where V: Vector<F>,
+    {
+        let radius = self.max_gap / F::cast(2);
+        let mut grid = Grid::new(radius.min(max_radius()), poisson_type);
+        for s in samples {
+            insert(&mut grid, s.clone(), poisson_type);
+        }
+        let uncovered = self.uncovered(&grid, poisson_type).len();
+        Coverage {
+            met: uncovered == 0,
+            uncovered,
+        }
+    }
+
+    /// Returns centers of the smallest cells which couldn't be verified to be covered.
+    /// Cells are checked with the same single disk test that `Ebeida` builds its `covered` check on,
+    /// but `covered` itself can't be used, because it also counts cells blocked by obstacles
+    /// and by points outside of the space as covered even though they can contain gaps.
+    fn uncovered<V>(&self, grid: &Grid<F, V>, poisson_type: Type) -> Vec<V>
+        where V: Vector<F>,
+    {
+        let mut result = vec![];
+        let mut stack = (0..grid.cells())
+            .filter_map(|c| decode::<F, V>(c, grid.side()))
+            .map(|c| (c, 0))
+            .collect::<Vec<_>>();
+        while let Some((index, level)) = stack.pop() {
+            let spacing = F::cast(1) / F::cast(grid.side() * 2usize.pow(level as u32));
+            if cell_within(grid, poisson_type, &index, spacing, self.max_gap) {
+                continue;
+            }
+            let mut center = index.clone();
+            for n in 0..V::dimension() {
+                center[n] = (center[n] + F::cast(1) / F::cast(2)) * spacing;
+            }
+            // If the center itself is in a gap there is no need to look any closer.
+            let sqgap = self.max_gap.powi(2);
+            let gap = grid.neighbours(&center, self.max_gap)
+                          .all(|s| sqdist(s.0.clone(), center.clone(), poisson_type) > sqgap);
+            if gap || level >= self.depth {
+                result.push(center);
+            } else {
+                let parent = index * F::cast(2);
+                stack.extend(each_combination(&[0, 1]).map(|t: V| (parent.clone() + t, level + 1)));
+            }
+        }
+        result
+    }
+}
+
+/// Inserts the sample to the grid.
+/// Without wrapping samples outside of the grid are put to the closest cell,
+/// which is still at least as close to every point of the space.
+fn insert<F, V>(grid: &mut Grid<F, V>, sample: V, poisson_type: Type)
+    where F: Float,
+          V: Vector<F>,
+{
+    let mut index = sample_to_index(&sample, grid.side());
+    if poisson_type == Type::Normal {
+        for n in 0..V::dimension() {
+            index[n] = index[n].max(F::cast(0)).min(F::cast(grid.side() - 1));
+        }
+    }
+    assert!(grid.insert(index, sample, F::cast(0)), "Index was clamped or wrapped to be within the grid.");
+}
+
+fn max_radius<F>() -> F
+    where F: Float,
+{
+    ::num_traits::NumCast::from(2f64.sqrt() / 2.).expect("Casting constant should always work.")
+}
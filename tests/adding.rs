@@ -1,8 +1,8 @@
 extern crate poisson;
-use poisson::{Type, Builder, algorithm};
+use poisson::{Type, Builder, Policy, algorithm};
 
 extern crate rand;
-use rand::SeedableRng;
+use rand::{Rng, SeedableRng};
 
 extern crate rand_xorshift;
 use rand_xorshift::XorShiftRng;
@@ -103,3 +103,172 @@ fn completely_filled_works() {
     };
     helper::test_with_samples_prefilled(samples, relative_radius, 100, Type::Normal, prefiller, Always);
 }
+
+#[test]
+fn adding_with_own_radius_keeps_samples_away() {
+    let radius = 0.02;
+    let points = [(Vect::new(0.5, 0.5), 0.2), (Vect::new(0.3, 0.8), 0.01), (Vect::new(-0.05, 0.3), 0.15)];
+    for &poisson_type in &[Type::Normal, Type::Perioditic] {
+        let mut iter = Builder::<_, Vect>::with_radius(radius, poisson_type)
+            .build(XorShiftRng::from_seed([1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16]), algorithm::Ebeida)
+            .into_iter();
+        for &(point, r) in &points {
+            iter.restrict_with_radius(point, r);
+        }
+        assert!(!iter.stays_legal(Vect::new(0.65, 0.5)));
+        // Points with smaller radius still keep samples as far as samples of the distribution do.
+        assert!(!iter.stays_legal(Vect::new(0.3, 0.815)));
+        assert!(iter.stays_legal(Vect::new(0.3, 0.845)));
+        let samples = iter.collect::<Vec<_>>();
+        assert!(samples.len() > 100);
+        for s in &samples {
+            for &(point, r) in &points {
+                let mut d = (s - point).abs();
+                if poisson_type == Type::Perioditic {
+                    d = d.map(|c| c.min(1. - c));
+                }
+                assert!(d.norm() >= r.max(2. * radius), "Sample {:?} is too close to {:?}", s, point);
+            }
+        }
+    }
+}
+
+#[test]
+fn removing_point_with_own_radius_frees_its_space() {
+    let radius = 0.02;
+    let point = Vect::new(0.5, 0.5);
+    for &poisson_type in &[Type::Normal, Type::Perioditic] {
+        let mut iter = Builder::<_, Vect>::with_radius(radius, poisson_type)
+            .build(XorShiftRng::from_seed([1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16]), algorithm::Ebeida)
+            .into_iter();
+        iter.restrict_with_radius(point, 0.2);
+        iter.restrict_with_radius(Vect::new(1.1, 0.5), 0.2);
+        let samples = iter.by_ref().collect::<Vec<_>>();
+        assert!(samples.iter().all(|s| (s - point).norm() >= 0.2));
+        assert!(iter.remove(point));
+        assert!(!iter.remove(point));
+        assert!(iter.remove(Vect::new(1.1, 0.5)));
+        let refilled = iter.collect::<Vec<_>>();
+        assert!(refilled.iter().any(|s| (s - point).norm() < 0.1));
+        let all = samples.into_iter().chain(refilled).collect::<Vec<_>>();
+        helper::assert_legal_poisson(&all, radius, algorithm::Ebeida);
+    }
+}
+
+#[test]
+fn restricting_reports_conflicts_by_policy() {
+    let radius = 0.05;
+    let mut iter = Builder::<_, Vect>::with_radius(radius, Type::Normal)
+        .build(XorShiftRng::from_seed([1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16]), algorithm::Ebeida)
+        .into_iter();
+    let first = Vect::new(0.5, 0.5);
+    let close = Vect::new(0.55, 0.5);
+    let far = Vect::new(0.8, 0.5);
+    let restrictions = iter.restrict_all(vec![first, close, far], Policy::Reject);
+    assert!(restrictions[0].inserted && restrictions[0].conflicts.is_empty());
+    assert!(!restrictions[1].inserted);
+    assert_eq!(vec![first], restrictions[1].conflicts);
+    assert!(restrictions[2].inserted && restrictions[2].conflicts.is_empty());
+    assert!(restrictions.iter().all(|r| r.evicted.is_empty()));
+
+    let evicting = iter.try_restrict(Vect::new(0.52, 0.5), Policy::Evict);
+    assert!(evicting.inserted);
+    assert_eq!(vec![first], evicting.evicted);
+    assert!(iter.stays_legal(Vect::new(0.41, 0.5)));
+
+    let forced = iter.try_restrict(Vect::new(0.85, 0.5), Policy::Force);
+    assert!(forced.inserted);
+    assert_eq!(vec![far], forced.conflicts);
+    assert!(!iter.stays_legal(Vect::new(0.9, 0.5)));
+
+    let samples = iter.collect::<Vec<_>>();
+    helper::assert_legal_poisson(&samples.iter().cloned().chain(vec![Vect::new(0.52, 0.5)]).collect(), radius, algorithm::Ebeida);
+}
+
+#[test]
+fn restricting_agrees_with_legality_of_samples() {
+    let radius = 0.03;
+    for &poisson_type in &[Type::Normal, Type::Perioditic] {
+        let mut rng = XorShiftRng::from_seed([1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16]);
+        let mut iter = Builder::<_, Vect>::with_radius(radius, poisson_type)
+            .build(rng.clone(), algorithm::Bridson)
+            .into_iter();
+        let mut inserted = 0;
+        for _ in 0..1000 {
+            let p = Vect::new(rng.gen(), rng.gen());
+            let legal = iter.stays_legal(p);
+            let restriction = iter.try_restrict(p, Policy::Reject);
+            assert_eq!(legal, restriction.inserted);
+            assert_eq!(legal, restriction.conflicts.is_empty());
+            if legal {
+                inserted += 1;
+            }
+        }
+        assert!(inserted > 100);
+    }
+}
+
+#[test]
+fn restricting_reports_conflicts_with_points_of_own_radius() {
+    let radius = 0.02;
+    let point = Vect::new(0.5, 0.5);
+    let sample = Vect::new(0.6, 0.5);
+    let mut iter = Builder::<_, Vect>::with_radius(radius, Type::Normal)
+        .build(XorShiftRng::from_seed([1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16]), algorithm::Ebeida)
+        .into_iter();
+    iter.restrict_with_radius(point, 0.2);
+    let rejected = iter.try_restrict(sample, Policy::Reject);
+    assert!(!rejected.inserted);
+    assert_eq!(vec![point], rejected.conflicts);
+
+    let evicting = iter.try_restrict(sample, Policy::Evict);
+    assert!(evicting.inserted);
+    assert_eq!(vec![point], evicting.evicted);
+    assert!(iter.stays_legal(Vect::new(0.5, 0.5)));
+    assert!(iter.try_restrict(Vect::new(0.45, 0.5), Policy::Reject).inserted);
+}
+
+#[test]
+fn bridson_covers_domain_around_prefilled_samples() {
+    let radius = 0.02;
+    let prefilled = vec![Vect::new(0.5, 0.5), Vect::new(0.2, 0.7), Vect::new(0.9, 0.1)];
+    for &seeding in &[false, true] {
+        let mut iter = Builder::<_, Vect>::with_radius(radius, Type::Normal)
+            .build(XorShiftRng::from_seed([1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16]), algorithm::Bridson)
+            .into_iter();
+        for &p in &prefilled {
+            if seeding {
+                iter.restrict_as_seed(p);
+            } else {
+                iter.restrict(p);
+            }
+        }
+        let samples = iter.collect::<Vec<_>>();
+        if seeding {
+            assert!(prefilled.iter().any(|p| (p - samples[0]).norm() <= 4. * radius));
+        }
+        let all = samples.iter().chain(&prefilled).cloned().collect::<Vec<_>>();
+        helper::assert_legal_poisson(&all, radius, algorithm::Bridson);
+        let probes = 100;
+        let uncovered = (0..probes * probes)
+            .map(|i| Vect::new((i % probes) as f64 / probes as f64, (i / probes) as f64 / probes as f64))
+            .filter(|p| all.iter().all(|s| (s - p).norm() >= 2. * radius))
+            .count();
+        assert!(uncovered < probes * probes / 100,
+                "{} of {} probes weren't covered.",
+                uncovered,
+                probes * probes);
+    }
+}
+
+#[test]
+fn bridson_without_restrictions_generates_as_before() {
+    // Output of the algorithm from before restricted samples were seeded and gaps were filled.
+    let samples = Builder::<_, Vect>::with_radius(0.02, Type::Normal)
+        .build(XorShiftRng::from_seed([1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16]), algorithm::Bridson)
+        .into_iter()
+        .collect::<Vec<_>>();
+    assert_eq!(191, samples.len());
+    assert_eq!(Vect::new(0.25021560516322805, 0.21705964434509828), samples[0]);
+    assert_eq!(Vect::new(0.9832937135412787, 0.4400355932038793), samples[190]);
+}
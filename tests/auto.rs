@@ -0,0 +1,44 @@
+extern crate poisson;
+use poisson::{Type, Builder};
+use poisson::algorithm::{Auto, Choice, Quality};
+
+extern crate rand;
+use rand::SeedableRng;
+
+extern crate rand_xorshift;
+use rand_xorshift::XorShiftRng;
+
+extern crate nalgebra as na;
+pub type Vect = na::Vector2<f64>;
+pub type Vect8 = na::VectorN<f64, na::U8>;
+
+mod helper;
+
+fn rng() -> XorShiftRng {
+    XorShiftRng::from_seed([1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16])
+}
+
+#[test]
+fn quality_preference_is_respected() {
+    let low = Builder::<_, Vect>::with_radius(0.05, Type::Normal);
+    let high = Builder::<_, Vect8>::with_radius(0.05, Type::Normal);
+    assert_eq!(Quality::Balanced, Auto::default().quality());
+    assert_eq!(Choice::Ebeida, Auto::new(Quality::Maximal).choose(&high));
+    assert_eq!(Choice::Bridson, Auto::new(Quality::Fast).choose(&low));
+    assert_eq!(Choice::Ebeida, Auto::default().choose(&low));
+    assert_eq!(Choice::Bridson, Auto::default().choose(&high));
+}
+
+#[test]
+fn chosen_algorithm_generates_valid_distribution() {
+    for &quality in &[Quality::Maximal, Quality::Balanced, Quality::Fast] {
+        let auto = Auto::new(quality);
+        let builder = Builder::<_, Vect>::with_radius(0.02, Type::Normal);
+        let expected = auto.choose(&builder);
+        let iter = builder.build(rng(), auto).into_iter();
+        assert_eq!(expected, iter.algorithm().choice());
+        let samples = iter.collect::<Vec<_>>();
+        assert!(!samples.is_empty());
+        helper::assert_legal_poisson(&samples, 0.02, auto);
+    }
+}
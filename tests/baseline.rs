@@ -0,0 +1,105 @@
+extern crate poisson;
+use poisson::baseline::{WhiteNoise, Jittered, LatinHypercube, Halton, Sobol};
+
+extern crate rand;
+use rand::SeedableRng;
+
+extern crate rand_xorshift;
+use rand_xorshift::XorShiftRng;
+
+extern crate nalgebra as na;
+pub type Vect = na::Vector2<f64>;
+pub type Vect3 = na::Vector3<f64>;
+
+fn rng() -> XorShiftRng {
+    XorShiftRng::from_seed([1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16])
+}
+
+fn assert_in_unit(samples: &[Vect3]) {
+    assert!(samples.iter().all(|s| s.iter().all(|&c| 0. <= c && c < 1.)));
+}
+
+#[test]
+fn samplers_generate_requested_amount() {
+    let samples: Vec<Vect3> = WhiteNoise::new(100).generate(&mut rng());
+    assert_eq!(100, samples.len());
+    assert_in_unit(&samples);
+    let samples: Vec<Vect3> = Jittered::new(4).generate(&mut rng());
+    assert_eq!(64, samples.len());
+    assert_in_unit(&samples);
+    let samples: Vec<Vect3> = LatinHypercube::new(100).generate(&mut rng());
+    assert_eq!(100, samples.len());
+    assert_in_unit(&samples);
+    let mut halton = Halton::new(100);
+    halton.set_rotate(true);
+    let samples: Vec<Vect3> = halton.generate(&mut rng());
+    assert_eq!(100, samples.len());
+    assert_in_unit(&samples);
+    let mut sobol = Sobol::new(100);
+    sobol.set_rotate(true);
+    let samples: Vec<Vect3> = sobol.generate(&mut rng());
+    assert_eq!(100, samples.len());
+    assert_in_unit(&samples);
+}
+
+#[test]
+fn jittered_has_one_sample_per_cell() {
+    let side = 7;
+    let mut cells = vec![0; side * side];
+    for s in Jittered::new(side).iter::<_, Vect, _>(rng()) {
+        let (x, y) = ((s.x * side as f64) as usize, (s.y * side as f64) as usize);
+        cells[x + y * side] += 1;
+    }
+    assert!(cells.iter().all(|&c| c == 1));
+}
+
+#[test]
+fn latin_hypercube_has_one_sample_per_stratum() {
+    let samples = 50;
+    let mut strata = vec![vec![0; samples]; 3];
+    for s in LatinHypercube::new(samples).iter::<_, Vect3, _>(rng()) {
+        for n in 0..3 {
+            strata[n][(s[n] * samples as f64) as usize] += 1;
+        }
+    }
+    assert!(strata.iter().all(|s| s.iter().all(|&c| c == 1)));
+}
+
+#[test]
+fn halton_matches_known_values() {
+    let expected = [
+        Vect::new(0., 0.),
+        Vect::new(1. / 2., 1. / 3.),
+        Vect::new(1. / 4., 2. / 3.),
+        Vect::new(3. / 4., 1. / 9.),
+    ];
+    let samples = Halton::new(4).generate::<_, Vect, _>(&mut rng());
+    assert_eq!(&expected[..], &samples[..]);
+}
+
+#[test]
+fn sobol_matches_known_values() {
+    let expected = [
+        Vect::new(0., 0.),
+        Vect::new(0.5, 0.5),
+        Vect::new(0.75, 0.25),
+        Vect::new(0.25, 0.75),
+        Vect::new(0.375, 0.375),
+        Vect::new(0.875, 0.875),
+    ];
+    let samples = Sobol::new(6).generate::<_, Vect, _>(&mut rng());
+    assert_eq!(&expected[..], &samples[..]);
+}
+
+#[test]
+fn sobol_is_stratified() {
+    // Every power of two prefix of Sobol sequence has one sample per elementary interval of that size.
+    let samples = 64;
+    let mut strata = vec![vec![0; samples]; 3];
+    for s in Sobol::new(samples).iter::<_, Vect3, _>(rng()) {
+        for n in 0..3 {
+            strata[n][(s[n] * samples as f64) as usize] += 1;
+        }
+    }
+    assert!(strata.iter().all(|s| s.iter().all(|&c| c == 1)));
+}
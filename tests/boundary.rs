@@ -0,0 +1,117 @@
+extern crate poisson;
+use poisson::{Type, Builder, algorithm};
+use poisson::boundary::{BoundaryFirst, Domain, Placed};
+
+extern crate rand;
+use rand::SeedableRng;
+
+extern crate rand_xorshift;
+use rand_xorshift::XorShiftRng;
+
+extern crate nalgebra as na;
+pub type Vect = na::Vector2<f64>;
+pub type Vect3 = na::Vector3<f64>;
+
+mod helper;
+
+fn rng() -> XorShiftRng {
+    XorShiftRng::from_seed([1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16])
+}
+
+fn on_boundary(sample: &Vect3) -> usize {
+    (0..3).filter(|&n| sample[n] == 0. || sample[n] == 1.).count()
+}
+
+#[test]
+fn box_boundary_is_sampled_before_interior() {
+    let radius = 0.05;
+    let generator = Builder::<_, Vect3>::with_radius(radius, Type::Normal).build(rng(), algorithm::Ebeida);
+    let samples = BoundaryFirst::new(Domain::Box).generate(generator);
+    assert!(samples.windows(2).all(|w| w[0].dimension <= w[1].dimension));
+    for k in 0..4 {
+        assert!(samples.iter().any(|s| s.dimension == k));
+    }
+    assert_eq!(8, samples.iter().filter(|s| s.dimension == 0).count());
+    for s in samples.iter().filter(|s| s.dimension < 3) {
+        assert_eq!(3 - s.dimension, on_boundary(&s.value));
+    }
+    // Each stage is maximal, so every point on the edges and the faces is covered by samples of the same or earlier stages.
+    for x in 0..41 {
+        for y in 0..41 {
+            for &(fixed, value) in &[(0, 0.), (0, 1.), (1, 0.), (1, 1.), (2, 0.), (2, 1.)] {
+                let mut probe = Vect3::zeros();
+                let free = (0..3).filter(|&n| n != fixed).collect::<Vec<_>>();
+                probe[fixed] = value;
+                probe[free[0]] = x as f64 / 40.;
+                probe[free[1]] = y as f64 / 40.;
+                let dimension = on_boundary(&probe);
+                assert!(samples.iter()
+                            .filter(|s| s.dimension <= 3 - dimension)
+                            .any(|s| (s.value - probe).norm() < 2. * radius),
+                        "{:?}", probe);
+            }
+        }
+    }
+    let samples = samples.into_iter().map(|s| s.value).collect::<Vec<_>>();
+    helper::assert_legal_poisson(&samples, radius, algorithm::Ebeida);
+}
+
+#[test]
+fn polygon_boundary_is_sampled_before_interior() {
+    let radius = 0.02;
+    let vertices = vec![Vect::new(0.1, 0.1), Vect::new(0.9, 0.2), Vect::new(0.5, 0.5), Vect::new(0.2, 0.9)];
+    let domain = BoundaryFirst::new(Domain::Polygon(vertices.clone()));
+    let generator = Builder::<_, Vect>::with_radius(radius, Type::Normal).build(rng(), algorithm::Ebeida);
+    let samples = domain.generate(generator);
+    let corners = samples.iter()
+        .filter(|s| s.dimension == 0)
+        .map(|s| s.value)
+        .collect::<Vec<_>>();
+    assert_eq!(vertices, corners);
+    assert!(samples.iter().any(|s| s.dimension == 1));
+    for &Placed { dimension, ref value } in &samples {
+        if dimension == 2 {
+            assert!(domain.contains(value));
+        }
+    }
+    assert!(!domain.contains(&Vect::new(0.8, 0.8)));
+    // Edges are sampled maximally before the interior.
+    for (i, a) in vertices.iter().enumerate() {
+        let b = vertices[(i + 1) % vertices.len()];
+        for t in 0..101 {
+            let probe = a + (b - a) * (t as f64 / 100.);
+            assert!(samples.iter().filter(|s| s.dimension <= 1).any(|s| (s.value - probe).norm() < 2. * radius),
+                    "{:?}", probe);
+        }
+    }
+    let interior = samples.iter().filter(|s| s.dimension == 2).count();
+    assert!(interior > 50, "{}", interior);
+    let samples = samples.into_iter().map(|s| s.value).collect::<Vec<_>>();
+    helper::assert_legal_poisson(&samples, radius, algorithm::Ebeida);
+}
+
+#[test]
+fn polygon_interior_is_covered_up_to_edges() {
+    let radius = 0.02;
+    let vertices = vec![Vect::new(0.1, 0.1), Vect::new(0.9, 0.2), Vect::new(0.5, 0.5), Vect::new(0.2, 0.9)];
+    let mut domain = BoundaryFirst::new(Domain::Polygon(vertices));
+    // Few throws per cell, so the edges are mostly sampled at the finer levels.
+    domain.set_throws(0.1);
+    let generator = Builder::<_, Vect>::with_radius(radius, Type::Normal).build(rng(), algorithm::Ebeida);
+    let samples = domain.generate(generator);
+    for &Placed { dimension, ref value } in &samples {
+        if dimension == 2 {
+            assert!(domain.contains(value));
+        }
+    }
+    for x in 0..401 {
+        for y in 0..401 {
+            let probe = Vect::new(x as f64 / 400., y as f64 / 400.);
+            if domain.contains(&probe) {
+                assert!(samples.iter().any(|s| (s.value - probe).norm() < 2. * radius), "{:?}", probe);
+            }
+        }
+    }
+    let samples = samples.into_iter().map(|s| s.value).collect::<Vec<_>>();
+    helper::assert_legal_poisson(&samples, radius, algorithm::Ebeida);
+}
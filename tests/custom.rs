@@ -0,0 +1,85 @@
+extern crate poisson;
+use poisson::{Type, Builder, Policy};
+use poisson::obstacle::Obstacle;
+use poisson::algorithm::{Algorithm, Creator};
+use poisson::temporal::Frame;
+
+extern crate rand;
+use rand::{Rng, SeedableRng};
+
+extern crate rand_xorshift;
+use rand_xorshift::XorShiftRng;
+
+extern crate nalgebra as na;
+use na::Vector2 as naVec2;
+pub type Vec2 = naVec2<f64>;
+
+/// Algorithm that implements only the required methods and generates a single sample.
+#[derive(Clone, Copy, Debug)]
+struct Single;
+
+struct SingleAlgo(Option<Vec2>);
+
+impl Creator<f64, Vec2> for Single {
+    type Algo = SingleAlgo;
+
+    fn create(_: &Builder<f64, Vec2>) -> Self::Algo {
+        SingleAlgo(Some(Vec2::new(0.5, 0.5)))
+    }
+}
+
+impl Algorithm<f64, Vec2> for SingleAlgo {
+    fn next<R>(&mut self, _: &mut Builder<f64, Vec2>, _: &mut R) -> Option<Vec2> where R: Rng {
+        self.0.take()
+    }
+
+    fn size_hint(&self, _: &Builder<f64, Vec2>) -> (usize, Option<usize>) {
+        let amount = self.0.iter().count();
+        (amount, Some(amount))
+    }
+
+    fn restrict(&mut self, _: Vec2) {}
+
+    fn stays_legal(&self, _: &Builder<f64, Vec2>, _: Vec2) -> bool {
+        true
+    }
+}
+
+fn rng() -> XorShiftRng {
+    XorShiftRng::from_seed([1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16])
+}
+
+#[test]
+fn algorithm_with_only_required_methods_generates() {
+    let samples = Builder::<_, Vec2>::with_radius(0.1, Type::Normal)
+        .build(rng(), Single)
+        .into_iter()
+        .collect::<Vec<_>>();
+    assert_eq!(vec![Vec2::new(0.5, 0.5)], samples);
+}
+
+#[test]
+fn algorithm_with_only_required_methods_reports_unsupported_restrictions() {
+    let mut iter = Builder::<_, Vec2>::with_radius(0.1, Type::Normal)
+        .build(rng(), Single)
+        .into_iter();
+    assert!(!iter.restrict_obstacle(Obstacle::Sphere(Vec2::new(0.5, 0.5), 0.1)));
+    assert!(!iter.restrict_domain(Obstacle::Sphere(Vec2::new(0.1, 0.1), 0.1)));
+    assert!(!iter.restrict_with_radius(Vec2::new(0.5, 0.5), 0.2));
+    assert!(!iter.remove(Vec2::new(0.5, 0.5)));
+    assert!(iter.refill_region(|_| true).is_empty());
+    let restriction = iter.try_restrict(Vec2::new(0.5, 0.5), Policy::Evict);
+    assert!(restriction.inserted);
+    assert!(restriction.conflicts.is_empty());
+    assert!(restriction.evicted.is_empty());
+    assert_eq!(vec![Vec2::new(0.5, 0.5)], iter.collect::<Vec<_>>());
+}
+
+#[test]
+fn algorithm_with_only_required_methods_drops_samples_outside_of_domain() {
+    let generator = || Builder::<_, Vec2>::with_radius(0.1, Type::Normal).build(rng(), Single);
+    let outside = Frame::new().next_within(generator(), Obstacle::Box(Vec2::new(0., 0.), Vec2::new(0.4, 1.)));
+    assert!(outside.samples().is_empty());
+    let inside = Frame::new().next_within(generator(), Obstacle::Box(Vec2::new(0., 0.), Vec2::new(0.6, 1.)));
+    assert_eq!(1, inside.samples().len());
+}
@@ -0,0 +1,134 @@
+#![cfg(feature = "delaunay")]
+extern crate poisson;
+use poisson::{Type, Builder, algorithm};
+use poisson::baseline::WhiteNoise;
+use poisson::delaunay::{triangulate, Simplex};
+
+extern crate rand;
+use rand::SeedableRng;
+
+extern crate rand_xorshift;
+use rand_xorshift::XorShiftRng;
+
+extern crate nalgebra as na;
+pub type Vect = na::Vector2<f64>;
+pub type Vect3 = na::Vector3<f64>;
+
+fn rng() -> XorShiftRng {
+    XorShiftRng::from_seed([1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16])
+}
+
+fn area(s: &Simplex<Vect>) -> f64 {
+    let (a, b) = (s.vertices[1] - s.vertices[0], s.vertices[2] - s.vertices[0]);
+    (a.x * b.y - a.y * b.x).abs() / 2.
+}
+
+fn volume(s: &Simplex<Vect3>) -> f64 {
+    let (a, b, c) = (s.vertices[1] - s.vertices[0], s.vertices[2] - s.vertices[0], s.vertices[3] - s.vertices[0]);
+    a.dot(&b.cross(&c)).abs() / 6.
+}
+
+fn circumcircle(s: &Simplex<Vect>) -> (Vect, f64) {
+    let (a, b, c) = (s.vertices[0], s.vertices[1], s.vertices[2]);
+    let d = 2. * (a.x * (b.y - c.y) + b.x * (c.y - a.y) + c.x * (a.y - b.y));
+    let center = Vect::new(
+        (a.norm_squared() * (b.y - c.y) + b.norm_squared() * (c.y - a.y) + c.norm_squared() * (a.y - b.y)) / d,
+        (a.norm_squared() * (c.x - b.x) + b.norm_squared() * (a.x - c.x) + c.norm_squared() * (b.x - a.x)) / d);
+    (center, (a - center).norm())
+}
+
+fn assert_empty_circles(samples: &[Vect], simplices: &[Simplex<Vect>], poisson_type: Type) {
+    let shifts = match poisson_type {
+        Type::Normal => vec![Vect::zeros()],
+        Type::Perioditic => (0..9).map(|i| Vect::new((i % 3) as f64 - 1., (i / 3) as f64 - 1.)).collect(),
+    };
+    for s in simplices {
+        let (center, radius) = circumcircle(s);
+        for sample in samples {
+            for shift in &shifts {
+                assert!((sample + shift - center).norm() >= radius - 1e-9);
+            }
+        }
+    }
+}
+
+#[test]
+fn triangulation_has_empty_circumcircles() {
+    let samples = Builder::<_, Vect>::with_radius(0.03, Type::Normal)
+        .build(rng(), algorithm::Ebeida)
+        .generate();
+    let triangulation = triangulate(&samples, Type::Normal);
+    assert_empty_circles(&samples, triangulation.simplices(), Type::Normal);
+    for i in 0..samples.len() {
+        assert!(triangulation.simplices().iter().any(|s| s.indices.contains(&i)));
+    }
+    let area = triangulation.simplices().iter().map(area).sum::<f64>();
+    assert!(0.8 < area && area <= 1.);
+}
+
+#[test]
+fn perioditic_triangulation_covers_torus() {
+    let samples = Builder::<_, Vect>::with_radius(0.04, Type::Perioditic)
+        .build(rng(), algorithm::Ebeida)
+        .generate();
+    let triangulation = triangulate(&samples, Type::Perioditic);
+    assert_eq!(2 * samples.len(), triangulation.simplices().len());
+    let area = triangulation.simplices().iter().map(area).sum::<f64>();
+    assert!((area - 1.).abs() < 1e-9, "{}", area);
+    assert_empty_circles(&samples, triangulation.simplices(), Type::Perioditic);
+}
+
+#[test]
+fn random_perioditic_triangulation_covers_torus() {
+    let samples = WhiteNoise::new(300).generate::<f64, Vect, _>(&mut rng());
+    let triangulation = triangulate(&samples, Type::Perioditic);
+    assert_eq!(2 * samples.len(), triangulation.simplices().len());
+    let area = triangulation.simplices().iter().map(area).sum::<f64>();
+    assert!((area - 1.).abs() < 1e-9, "{}", area);
+    assert_empty_circles(&samples, triangulation.simplices(), Type::Perioditic);
+}
+
+#[test]
+fn tetrahedralisation_fills_space() {
+    let samples = Builder::<_, Vect3>::with_radius(0.08, Type::Perioditic)
+        .build(rng(), algorithm::Ebeida)
+        .generate();
+    let triangulation = triangulate(&samples, Type::Perioditic);
+    let volume = triangulation.simplices().iter().map(volume).sum::<f64>();
+    assert!((volume - 1.).abs() < 1e-9, "{}", volume);
+    assert!(triangulation.statistics().min_angle.is_none());
+}
+
+#[test]
+fn cospherical_grids_are_triangulated() {
+    let samples = (0..6 * 6)
+        .map(|i| Vect::new((i % 6) as f64, (i / 6) as f64) / 6.)
+        .collect::<Vec<_>>();
+    let triangulation = triangulate(&samples, Type::Perioditic);
+    assert_eq!(2 * samples.len(), triangulation.simplices().len());
+    let total = triangulation.simplices().iter().map(area).sum::<f64>();
+    assert!((total - 1.).abs() < 1e-9, "{}", total);
+    let samples = (0..7 * 7 * 7)
+        .map(|i| Vect3::new((i % 7) as f64, (i / 7 % 7) as f64, (i / 49) as f64) / 7.)
+        .collect::<Vec<_>>();
+    for &poisson_type in &[Type::Normal, Type::Perioditic] {
+        let triangulation = triangulate(&samples, poisson_type);
+        let total = triangulation.simplices().iter().map(volume).sum::<f64>();
+        let expected = if poisson_type == Type::Normal { (6. / 7.) * (6. / 7.) * (6. / 7.) } else { 1. };
+        assert!((total - expected).abs() < 1e-9, "{}", total);
+        assert!(triangulation.simplices().iter().all(|s| volume(s) > 1e-9));
+    }
+}
+
+#[test]
+fn poisson_disk_triangles_are_better_than_random() {
+    let poisson = Builder::<_, Vect>::with_radius(0.03, Type::Perioditic)
+        .build(rng(), algorithm::Ebeida)
+        .generate();
+    let random = WhiteNoise::new(poisson.len()).generate::<f64, Vect, _>(&mut rng());
+    let poisson = triangulate(&poisson, Type::Perioditic).statistics();
+    let random = triangulate(&random, Type::Perioditic).statistics();
+    assert!(poisson.min_angle.unwrap() > random.min_angle.unwrap());
+    assert!(poisson.mean_radius_edge < random.mean_radius_edge);
+    assert!(poisson.max_radius_edge < random.max_radius_edge);
+}
@@ -0,0 +1,100 @@
+extern crate poisson;
+use poisson::dither::VoidAndCluster;
+
+extern crate rand;
+use rand::SeedableRng;
+
+extern crate rand_xorshift;
+use rand_xorshift::XorShiftRng;
+
+extern crate nalgebra as na;
+
+fn rng() -> XorShiftRng {
+    XorShiftRng::from_seed([1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16])
+}
+
+fn toroidal_sqdist(side: usize, p1: &[usize], p2: &[usize]) -> usize {
+    p1.iter().zip(p2).map(|(&a, &b)| {
+        let d = if a < b { b - a } else { a - b };
+        let d = d.min(side - d);
+        d * d
+    }).sum()
+}
+
+#[test]
+fn ranks_are_permutation_and_thresholds_are_uniform() {
+    let mask = VoidAndCluster::<_, na::Vector2<f64>>::new(32).generate(&mut rng());
+    let mut ranks = mask.ranks().to_vec();
+    ranks.sort();
+    assert_eq!((0..32 * 32).collect::<Vec<_>>(), ranks);
+    let mut histogram = vec![0; 256];
+    for v in mask.to_u8() {
+        histogram[v as usize] += 1;
+    }
+    assert!(histogram.iter().all(|&h| h == 4));
+    let thresholds = mask.to_u16();
+    assert_eq!(0, *thresholds.iter().min().unwrap());
+    assert_eq!(65536 - 64, *thresholds.iter().max().unwrap() as usize);
+}
+
+#[test]
+fn threshold_prefixes_are_spread() {
+    let side = 32;
+    let mask = VoidAndCluster::<_, na::Vector2<f64>>::new(side).generate(&mut rng());
+    for &amount in &[32, 128, 256] {
+        let pixels = (0..side)
+            .flat_map(|y| (0..side).map(move |x| vec![x, y]))
+            .filter(|p| mask.rank(p) < amount)
+            .collect::<Vec<_>>();
+        assert_eq!(amount, pixels.len());
+        // Pixels of a prefix should be roughly as far from each other as in a regular grid.
+        let spacing = (side * side / amount) as f64;
+        for (i, p1) in pixels.iter().enumerate() {
+            for p2 in &pixels[i + 1..] {
+                assert!(toroidal_sqdist(side, p1, p2) as f64 >= spacing / 4., "{}", amount);
+            }
+        }
+    }
+}
+
+#[test]
+fn three_dimensional_mask_works() {
+    let mask = VoidAndCluster::<_, na::Vector3<f64>>::new(8).generate(&mut rng());
+    assert_eq!(3, mask.dimension());
+    let mut ranks = mask.ranks().to_vec();
+    ranks.sort();
+    assert_eq!((0..8 * 8 * 8).collect::<Vec<_>>(), ranks);
+    assert_eq!(mask.ranks()[1 + 2 * 8 + 3 * 64], mask.rank(&[1, 2, 3]));
+}
+
+#[test]
+fn dense_initial_pattern_leaves_a_void() {
+    let mut generator = VoidAndCluster::<_, na::Vector2<f64>>::new(2);
+    generator.set_initial(0.9);
+    let mask = generator.generate(&mut rng());
+    let mut ranks = mask.ranks().to_vec();
+    ranks.sort();
+    assert_eq!(vec![0, 1, 2, 3], ranks);
+}
+
+#[test]
+fn larger_three_dimensional_mask_is_spread() {
+    let side = 24;
+    let mask = VoidAndCluster::<_, na::Vector3<f64>>::new(side).generate(&mut rng());
+    let mut ranks = mask.ranks().to_vec();
+    ranks.sort();
+    assert_eq!((0..side * side * side).collect::<Vec<_>>(), ranks);
+    for &spacing in &[6, 4] {
+        let amount = side * side * side / (spacing * spacing * spacing);
+        let pixels = (0..side)
+            .flat_map(|z| (0..side).flat_map(move |y| (0..side).map(move |x| vec![x, y, z])))
+            .filter(|p| mask.rank(p) < amount)
+            .collect::<Vec<_>>();
+        assert_eq!(amount, pixels.len());
+        for (i, p1) in pixels.iter().enumerate() {
+            for p2 in &pixels[i + 1..] {
+                assert!(toroidal_sqdist(side, p1, p2) >= spacing * spacing / 4, "{}", amount);
+            }
+        }
+    }
+}
@@ -0,0 +1,125 @@
+extern crate poisson;
+use poisson::{Type, Builder, algorithm};
+use poisson::doe::{Axis, Design, Value};
+
+extern crate rand;
+use rand::SeedableRng;
+
+extern crate rand_xorshift;
+use rand_xorshift::XorShiftRng;
+
+extern crate nalgebra as na;
+pub type Vect3 = na::Vector3<f64>;
+
+fn rng() -> XorShiftRng {
+    XorShiftRng::from_seed([1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16])
+}
+
+fn design() -> Design<f64, Vect3> {
+    Design::new(vec![Axis::Log(1e-4, 1.), Axis::Integer(-2, 2), Axis::Categorical(3)])
+}
+
+#[test]
+fn decoded_values_are_typed_and_within_range() {
+    let design = design();
+    let generator = Builder::with_radius(0.08, Type::Normal).build(rng(), algorithm::Ebeida);
+    let experiments = design.generate(generator);
+    assert!(!experiments.is_empty());
+    let mut integers = vec![0; 5];
+    let mut categories = vec![0; 3];
+    for e in &experiments {
+        match e[0] {
+            Value::Real(v) => assert!(1e-4 <= v && v <= 1.),
+            _ => panic!("Logarithmic axis should decode to real value."),
+        }
+        match e[1] {
+            Value::Integer(v) => integers[(v + 2) as usize] += 1,
+            _ => panic!("Integer axis should decode to integer value."),
+        }
+        match e[2] {
+            Value::Category(v) => categories[v] += 1,
+            _ => panic!("Categorical axis should decode to category."),
+        }
+    }
+    assert!(integers.iter().all(|&c| c > 0));
+    assert!(categories.iter().all(|&c| c > 0));
+}
+
+#[test]
+fn normalised_samples_are_snapped_and_legal() {
+    let design = design();
+    let radius = 0.08;
+    let generator = Builder::with_radius(radius, Type::Normal).build(rng(), algorithm::Ebeida);
+    let samples = design.generate_normalised(generator);
+    for (i, s1) in samples.iter().enumerate() {
+        assert_eq!(*s1, design.snap(s1));
+        for s2 in &samples[i + 1..] {
+            assert!((s1 - s2).norm() >= 2. * radius);
+        }
+    }
+}
+
+#[test]
+fn normalised_samples_are_maximal_over_discrete_values() {
+    let design = design();
+    let radius = 0.08;
+    let generator = Builder::with_radius(radius, Type::Normal).build(rng(), algorithm::Ebeida);
+    let samples = design.generate_normalised(generator);
+    for x in 0..201 {
+        for integer in -2..3 {
+            for category in 0..3 {
+                let probe = design.encode(&[Value::Real(10f64.powf(x as f64 / 50. - 4.)),
+                                            Value::Integer(integer),
+                                            Value::Category(category)]);
+                assert!(samples.iter().any(|s| (s - probe).norm() < 2. * radius),
+                        "{:?} could still be added.", probe);
+            }
+        }
+    }
+}
+
+#[test]
+fn encoding_and_decoding_match() {
+    let design = design();
+    let values = vec![Value::Real(0.01), Value::Integer(1), Value::Category(2)];
+    let normalised = design.encode(&values);
+    assert!((normalised.x - 0.5).abs() < 1e-12);
+    assert_eq!(0.7, normalised.y);
+    assert_eq!(5. / 6., normalised.z);
+    let decoded = design.decode(&normalised);
+    match decoded[0] {
+        Value::Real(v) => assert!((v - 0.01).abs() < 1e-12),
+        _ => panic!("Logarithmic axis should decode to real value."),
+    }
+    assert_eq!(&values[1..], &decoded[1..]);
+}
+
+#[test]
+fn bridson_generates_snapped_and_legal_samples() {
+    let design = design();
+    let radius = 0.08;
+    let generator = Builder::with_radius(radius, Type::Normal).build(rng(), algorithm::Bridson);
+    let samples = design.generate_normalised(generator);
+    assert!(!samples.is_empty());
+    for (i, s1) in samples.iter().enumerate() {
+        assert_eq!(*s1, design.snap(s1));
+        for s2 in &samples[i + 1..] {
+            assert!((s1 - s2).norm() >= 2. * radius);
+        }
+    }
+}
+
+#[test]
+fn wide_integer_axes_decode_within_range() {
+    let axis = Axis::<f64>::Integer(-1_000_000_000_000_000_000, 1_000_000_000_000_000_000);
+    assert_eq!(Value::Integer(-1_000_000_000_000_000_000), axis.decode(0.));
+    assert_eq!(Value::Integer(1_000_000_000_000_000_000), axis.decode(1.));
+}
+
+#[test]
+#[should_panic]
+fn integer_axis_with_too_many_values_is_rejected() {
+    Design::<f64, Vect3>::new(vec![Axis::Integer(i64::min_value(), i64::max_value()),
+                                   Axis::Categorical(2),
+                                   Axis::Categorical(2)]);
+}
@@ -0,0 +1,78 @@
+extern crate poisson;
+use poisson::Type;
+use poisson::gibbs::Strauss;
+
+extern crate rand;
+use rand::SeedableRng;
+
+extern crate rand_xorshift;
+use rand_xorshift::XorShiftRng;
+
+extern crate nalgebra as na;
+pub type Vect = na::Vector2<f64>;
+
+fn assert_min_distance(samples: &[Vect], distance: f64) {
+    for (i, v1) in samples.iter().enumerate() {
+        for v2 in &samples[i + 1..] {
+            assert!((v1 - v2).norm() >= distance,
+                    "Samples {:?} and {:?} are closer than {} to each other.", v1, v2, distance);
+        }
+    }
+}
+
+#[test]
+fn hard_core_strauss_respects_hard_core() {
+    let mut rand = XorShiftRng::from_seed([1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16]);
+    let strauss = Strauss::<_, Vect>::with_hard_core(200., 0.03, 0.08, 0.5, Type::Normal);
+    let samples = strauss.generate(&mut rand);
+    assert!(!samples.is_empty());
+    assert_min_distance(&samples, 0.03);
+}
+
+#[test]
+fn strauss_without_strength_is_hard_disk() {
+    let mut rand = XorShiftRng::from_seed([1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16]);
+    let strauss = Strauss::<_, Vect>::new(100., 0.05, 0., Type::Normal);
+    let samples = strauss.generate(&mut rand);
+    assert!(!samples.is_empty());
+    assert_min_distance(&samples, 0.05);
+}
+
+#[test]
+fn strauss_with_full_strength_is_poisson() {
+    let mut rand = XorShiftRng::from_seed([1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16]);
+    let intensity = 50.;
+    let strauss = Strauss::<_, Vect>::new(intensity, 0.1, 1., Type::Perioditic);
+    let runs = 50;
+    let total: usize = (0..runs).map(|_| strauss.generate(&mut rand).len()).sum();
+    let mean = total as f64 / runs as f64;
+    assert!((mean - intensity).abs() < 5., "Mean amount of samples {} was too far from {}.", mean, intensity);
+}
+
+#[test]
+fn strauss_repulsion_reduces_close_pairs() {
+    let close_pairs = |strength| {
+        let mut rand = XorShiftRng::from_seed([1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16]);
+        let strauss = Strauss::<_, Vect>::new(100., 0.08, strength, Type::Perioditic);
+        (0..10)
+            .map(|_| {
+                let samples = strauss.generate(&mut rand);
+                samples.iter()
+                       .enumerate()
+                       .flat_map(|(i, v1)| samples[i + 1..].iter().map(move |v2| (v1 - v2).norm()))
+                       .filter(|&d| d < 0.08)
+                       .count()
+            })
+            .sum::<usize>()
+    };
+    assert!(close_pairs(0.2) < close_pairs(1.));
+}
+
+#[test]
+fn simulating_drops_invalid_initial_samples() {
+    let mut rand = XorShiftRng::from_seed([1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16]);
+    let mut strauss = Strauss::<_, Vect>::with_hard_core(100., 0.05, 0.08, 0.5, Type::Normal);
+    strauss.set_iterations(0);
+    let initial = vec![Vect::new(0.5, 0.5), Vect::new(0.51, 0.5), Vect::new(1.5, 0.5), Vect::new(0.2, 0.2)];
+    assert_eq!(vec![Vect::new(0.5, 0.5), Vect::new(0.2, 0.2)], strauss.simulate(&mut rand, initial));
+}
@@ -0,0 +1,77 @@
+extern crate poisson;
+use poisson::Type;
+use poisson::lattice::Lattice;
+
+extern crate rand;
+use rand::SeedableRng;
+
+extern crate rand_xorshift;
+use rand_xorshift::XorShiftRng;
+
+fn rng() -> XorShiftRng {
+    XorShiftRng::from_seed([1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16])
+}
+
+fn sites(lattice: &Lattice) -> Vec<Vec<usize>> {
+    let mut result = vec![vec![]];
+    for &size in lattice.size() {
+        result = result.into_iter()
+            .flat_map(|s| (0..size).map(move |c| {
+                let mut s = s.clone();
+                s.push(c);
+                s
+            }))
+            .collect();
+    }
+    result
+}
+
+fn assert_maximal(lattice: &Lattice, samples: &[Vec<usize>]) {
+    let sqdistance = lattice.squared_distance();
+    for (i, s1) in samples.iter().enumerate() {
+        for s2 in &samples[i + 1..] {
+            assert!(lattice.sqdist(s1, s2) >= sqdistance, "{:?} {:?}", s1, s2);
+        }
+    }
+    for site in sites(lattice) {
+        assert!(samples.iter().any(|s| lattice.sqdist(s, &site) < sqdistance), "{:?} is free", site);
+    }
+}
+
+#[test]
+fn lattice_distribution_is_maximal() {
+    for &poisson_type in &[Type::Normal, Type::Perioditic] {
+        let lattice = Lattice::new(vec![40, 30], 4, poisson_type);
+        assert_maximal(&lattice, &lattice.generate(&mut rng()));
+        let lattice = Lattice::with_real_distance(vec![12, 10, 8], 2.5, poisson_type);
+        assert_eq!(7, lattice.squared_distance());
+        assert_maximal(&lattice, &lattice.generate(&mut rng()));
+    }
+}
+
+#[test]
+fn distance_larger_than_half_of_perioditic_lattice_works() {
+    let lattice = Lattice::new(vec![7, 5], 4, Type::Perioditic);
+    let samples = lattice.generate(&mut rng());
+    assert_maximal(&lattice, &samples);
+}
+
+#[test]
+fn restricted_sites_are_respected() {
+    let lattice = Lattice::new(vec![20, 20], 3, Type::Normal);
+    let mut iter = lattice.iter(rng());
+    let restricted = vec![vec![0, 0], vec![10, 10], vec![11, 10]];
+    for r in &restricted {
+        iter.restrict(r);
+    }
+    assert!(!iter.stays_legal(&[12, 11]));
+    assert!(iter.stays_legal(&[19, 19]));
+    let samples = iter.collect::<Vec<_>>();
+    for s in &samples {
+        assert!(restricted.iter().all(|r| lattice.sqdist(r, s) >= 9));
+    }
+    let all = restricted.into_iter().chain(samples).collect::<Vec<_>>();
+    for site in sites(&lattice) {
+        assert!(all.iter().any(|s| lattice.sqdist(s, &site) < 9));
+    }
+}
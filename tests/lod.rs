@@ -0,0 +1,47 @@
+extern crate poisson;
+use poisson::{Type, algorithm};
+use poisson::lod::Nested;
+
+extern crate rand;
+use rand::SeedableRng;
+
+extern crate rand_xorshift;
+use rand_xorshift::XorShiftRng;
+
+extern crate nalgebra as na;
+pub type Vect = na::Vector2<f64>;
+
+mod helper;
+
+fn rng() -> XorShiftRng {
+    XorShiftRng::from_seed([1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16])
+}
+
+fn every_prefix_of_levels_is_valid<A>(algo: A)
+    where A: algorithm::Creator<f64, Vect> + Copy,
+{
+    for &poisson_type in &[Type::Normal, Type::Perioditic] {
+        let radii = vec![0.2, 0.1, 0.05];
+        let nested = Nested::<_, Vect>::new(radii.clone(), poisson_type);
+        let samples = nested.generate(&mut rng(), algo);
+        assert!(samples.windows(2).all(|w| w[0].level <= w[1].level));
+        for (level, &radius) in radii.iter().enumerate() {
+            let prefix = samples.iter()
+                .filter(|s| s.level <= level)
+                .map(|s| s.value)
+                .collect::<Vec<_>>();
+            assert!(samples.iter().any(|s| s.level == level));
+            helper::test_poisson(prefix.into_iter(), radius, poisson_type, algo);
+        }
+    }
+}
+
+#[test]
+fn every_prefix_of_levels_is_valid_with_ebeida() {
+    every_prefix_of_levels_is_valid(algorithm::Ebeida);
+}
+
+#[test]
+fn every_prefix_of_levels_is_valid_with_bridson() {
+    every_prefix_of_levels_is_valid(algorithm::Bridson);
+}
@@ -0,0 +1,64 @@
+extern crate poisson;
+use poisson::{Type, Builder, algorithm};
+use poisson::obstacle::Obstacle;
+
+extern crate rand;
+use rand::{Rng, SeedableRng};
+
+extern crate rand_xorshift;
+use rand_xorshift::XorShiftRng;
+
+extern crate nalgebra as na;
+pub type Vect = na::Vector2<f64>;
+
+fn rng(seed: u8) -> XorShiftRng {
+    XorShiftRng::from_seed([seed, 0, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1])
+}
+
+/// Checks that no point of a fine grid over the space could be added to the exhausted distribution.
+fn assert_maximal(radius: f64, poisson_type: Type, seed: u8, restricted: &[Vect], obstacles: &[Obstacle<f64, Vect>]) {
+    let mut iter = Builder::<_, Vect>::with_radius(radius, poisson_type)
+        .build(rng(seed), algorithm::Ebeida)
+        .into_iter();
+    for &sample in restricted {
+        if iter.stays_legal(sample) {
+            iter.restrict(sample);
+        }
+    }
+    for o in obstacles {
+        iter.restrict_obstacle(o.clone());
+    }
+    iter.by_ref().for_each(drop);
+    let steps = (4. / radius).ceil() as usize;
+    for x in 0..steps {
+        for y in 0..steps {
+            let probe = Vect::new(x as f64, y as f64) / steps as f64;
+            assert!(!iter.stays_legal(probe),
+                    "{:?} was left uncovered with radius {} and {:?}", probe, radius, poisson_type);
+        }
+    }
+}
+
+#[test]
+fn ebeida_is_maximal() {
+    for &poisson_type in &[Type::Normal, Type::Perioditic] {
+        for (seed, &radius) in [0.2, 0.1, 0.07, 0.03, 0.01].iter().enumerate() {
+            assert_maximal(radius, poisson_type, seed as u8, &[], &[]);
+        }
+    }
+}
+
+#[test]
+fn ebeida_is_maximal_around_restrictions() {
+    let obstacles = [
+        Obstacle::Box(Vect::new(0.6, 0.7), Vect::new(0.8, 0.9)),
+        Obstacle::Segment(Vect::new(0., 0.3), Vect::new(1., 0.45)),
+    ];
+    for &poisson_type in &[Type::Normal, Type::Perioditic] {
+        for seed in 0..5 {
+            let mut pre = rng(seed + 100);
+            let restricted = (0..40).map(|_| Vect::new(pre.gen(), pre.gen())).collect::<Vec<_>>();
+            assert_maximal(0.05, poisson_type, seed, &restricted, &obstacles);
+        }
+    }
+}
@@ -0,0 +1,106 @@
+extern crate poisson;
+use poisson::{Type, Builder, algorithm};
+use poisson::obstacle::Obstacle;
+
+extern crate rand;
+use rand::SeedableRng;
+
+extern crate rand_xorshift;
+use rand_xorshift::XorShiftRng;
+
+extern crate nalgebra as na;
+pub type Vect = na::Vector2<f64>;
+
+mod helper;
+
+fn rng() -> XorShiftRng {
+    XorShiftRng::from_seed([1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16])
+}
+
+fn obstacles() -> Vec<Obstacle<f64, Vect>> {
+    vec![
+        Obstacle::Polyline(vec![Vect::new(0., 0.2), Vect::new(0.5, 0.5), Vect::new(1., 0.6)]),
+        Obstacle::Box(Vect::new(0.6, 0.7), Vect::new(0.8, 0.9)),
+        Obstacle::Polygon(vec![Vect::new(0.1, 0.6), Vect::new(0.3, 0.6), Vect::new(0.2, 0.9)]),
+        Obstacle::Sphere(Vect::new(0.8, 0.2), 0.1),
+        Obstacle::Segment(Vect::new(0.95, 0.), Vect::new(0.95, 1.)),
+    ]
+}
+
+fn obstructed<A>(poisson_type: Type, algo: A)
+    where A: algorithm::Creator<f64, Vect>,
+{
+    let radius = 0.02;
+    let mut iter = Builder::<_, Vect>::with_radius(radius, poisson_type)
+        .build(rng(), algo)
+        .into_iter();
+    for o in obstacles() {
+        iter.restrict_obstacle(o);
+    }
+    assert!(!iter.stays_legal(Vect::new(0.7, 0.8)));
+    assert!(!iter.stays_legal(Vect::new(0.5, 0.51)));
+    let samples = iter.collect::<Vec<_>>();
+    assert!(samples.len() > 100);
+    for s in &samples {
+        for o in &obstacles() {
+            assert!(o.wrapped_distance(s, poisson_type) >= radius,
+                    "Sample {:?} is too close to obstacle {:?} using the '{:?}' algorithm.",
+                    s,
+                    o,
+                    algo);
+        }
+    }
+    if poisson_type == Type::Normal {
+        helper::assert_legal_poisson(&samples, radius, algo);
+    }
+}
+
+#[test]
+fn samples_avoid_obstacles() {
+    obstructed(Type::Normal, algorithm::Ebeida);
+    obstructed(Type::Normal, algorithm::Bridson);
+}
+
+#[test]
+fn perioditic_samples_avoid_wrapping_obstacles() {
+    obstructed(Type::Perioditic, algorithm::Ebeida);
+    obstructed(Type::Perioditic, algorithm::Bridson);
+}
+
+#[test]
+fn samples_stay_within_domain_up_to_its_border() {
+    let radius = 0.02;
+    let domain = Obstacle::Polygon(vec![Vect::new(0.1, 0.1), Vect::new(0.9, 0.2), Vect::new(0.4, 0.8)]);
+    let mut iter = Builder::<_, Vect>::with_radius(radius, Type::Normal)
+        .build(rng(), algorithm::Ebeida)
+        .into_iter();
+    iter.restrict_domain(domain.clone());
+    assert!(!iter.stays_legal(Vect::new(0.8, 0.8)));
+    let samples = iter.collect::<Vec<_>>();
+    assert!(samples.iter().all(|s| domain.distance(s) == 0.));
+    for x in 0..101 {
+        for y in 0..101 {
+            let probe = Vect::new(x as f64 / 100., y as f64 / 100.);
+            if domain.distance(&probe) == 0. {
+                assert!(samples.iter().any(|s| (s - probe).norm() < 2. * radius), "{:?}", probe);
+            }
+        }
+    }
+    helper::assert_legal_poisson(&samples, radius, algorithm::Ebeida);
+}
+
+#[test]
+fn domain_outside_of_the_grid_generates_nothing() {
+    for &poisson_type in &[Type::Normal, Type::Perioditic] {
+        let mut iter = Builder::<_, Vect>::with_radius(0.1, poisson_type)
+            .build(rng(), algorithm::Bridson)
+            .into_iter();
+        iter.restrict_domain(Obstacle::Sphere(Vect::new(5., 5.), 0.1));
+        assert_eq!(None, iter.next());
+        let mut iter = Builder::<_, Vect>::with_radius(0.1, poisson_type)
+            .build(rng(), algorithm::Ebeida)
+            .into_iter();
+        iter.restrict_domain(Obstacle::Sphere(Vect::new(5., 5.), 0.1));
+        assert_eq!(None, iter.next());
+    }
+}
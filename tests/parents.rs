@@ -0,0 +1,124 @@
+extern crate poisson;
+use poisson::{Type, Builder, algorithm};
+use poisson::algorithm::Algorithm;
+
+extern crate rand;
+use rand::{Rng, SeedableRng};
+
+extern crate rand_xorshift;
+use rand_xorshift::XorShiftRng;
+
+extern crate nalgebra as na;
+pub type Vect = na::Vector2<f64>;
+
+fn rng() -> XorShiftRng {
+    XorShiftRng::from_seed([1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16])
+}
+
+#[test]
+fn bridson_samples_form_spawn_tree() {
+    let radius = 0.02;
+    let samples = Builder::<_, Vect>::with_radius(radius, Type::Normal)
+        .build(rng(), algorithm::Bridson)
+        .into_iter()
+        .with_parents()
+        .collect::<Vec<_>>();
+    assert!(samples.len() > 1);
+    assert_eq!(None, samples[0].1);
+    // Without restrictions every sample is spawned from the first one, so there are no gaps to fill.
+    for (i, &(sample, parent)) in samples.iter().enumerate().skip(1) {
+        let parent = parent.expect("Only the first sample should be a seed.");
+        assert!(parent < i);
+        let distance = (samples[parent].0 - sample).norm();
+        assert!(2. * radius <= distance && distance <= 4. * radius);
+    }
+}
+
+#[test]
+fn ebeida_samples_are_seeds() {
+    let samples = Builder::<_, Vect>::with_radius(0.05, Type::Normal)
+        .build(rng(), algorithm::Ebeida)
+        .into_iter()
+        .with_parents()
+        .collect::<Vec<_>>();
+    assert!(!samples.is_empty());
+    assert!(samples.iter().all(|&(_, p)| p.is_none()));
+}
+
+#[test]
+fn samples_carry_metadata_and_payload() {
+    let samples = Builder::<_, Vect>::with_radius(0.05, Type::Normal)
+        .build(rng(), algorithm::Ebeida)
+        .into_iter()
+        .with_payload(|v, rng| (v.x + v.y, rng.gen::<f64>()))
+        .collect::<Vec<_>>();
+    assert!(!samples.is_empty());
+    for (i, s) in samples.iter().enumerate() {
+        assert_eq!(i, s.index);
+        assert_eq!(None, s.parent);
+        assert!(s.level.is_some());
+        assert_eq!(s.value.x + s.value.y, s.payload.0);
+        assert!(0. <= s.payload.1 && s.payload.1 < 1.);
+    }
+    assert!(samples.windows(2).all(|w| w[0].level <= w[1].level));
+
+    let samples = Builder::<_, Vect>::with_radius(0.05, Type::Normal)
+        .build(rng(), algorithm::Bridson)
+        .into_iter()
+        .with_payload(|_, _| ())
+        .collect::<Vec<_>>();
+    assert!(samples.iter().all(|s| s.level.is_none()));
+    assert!(samples.iter().skip(1).all(|s| s.parent.is_some()));
+}
+
+#[test]
+fn indices_of_samples_match_indices_of_parents() {
+    let radius = 0.05;
+    let mut iter = Builder::<_, Vect>::with_radius(radius, Type::Normal)
+        .build(rng(), algorithm::Bridson)
+        .into_iter();
+    let mut values = iter.by_ref().take(10).collect::<Vec<_>>();
+    let samples = iter.with_payload(|_, _| ()).collect::<Vec<_>>();
+    assert_eq!(10, samples[0].index);
+    values.extend(samples.iter().map(|s| s.value));
+    assert!(samples.iter().any(|s| s.parent.map_or(false, |p| p < 10)));
+    for s in &samples {
+        assert_eq!(values[s.index], s.value);
+        if let Some(p) = s.parent {
+            assert!(p < s.index);
+            let distance = (values[p] - s.value).norm();
+            assert!(2. * radius <= distance && distance <= 4. * radius, "{}", distance);
+        }
+    }
+}
+
+#[test]
+fn refilled_samples_keep_their_parents() {
+    let radius = 0.02;
+    let mut iter = Builder::<_, Vect>::with_radius(radius, Type::Normal)
+        .build(rng(), algorithm::Bridson)
+        .into_iter();
+    let mut values = iter.by_ref().collect::<Vec<_>>();
+    let mut spawned_from_neighbours = false;
+    for &target in &[Vect::new(0.3, 0.3), Vect::new(0.7, 0.7)] {
+        let removed = iter.refill_region(|v| (v - target).norm() < 0.1);
+        assert!(!removed.is_empty());
+        let generated = values.len();
+        while let Some(sample) = iter.next() {
+            // Refilling stays within the space freed by the latest removal.
+            assert!((sample - target).norm() < 0.1 + 8. * radius);
+            if let Some(parent) = iter.algorithm().parent() {
+                assert!(parent < values.len());
+                let distance = (values[parent] - sample).norm();
+                assert!(2. * radius <= distance && distance <= 4. * radius, "{}", distance);
+                // Samples that weren't placed to gaps are spawned from the neighbours of the removed samples.
+                if parent < generated {
+                    spawned_from_neighbours = true;
+                }
+            }
+            values.push(sample);
+        }
+        assert!(values.len() > generated);
+    }
+    assert!(spawned_from_neighbours);
+}
@@ -0,0 +1,49 @@
+extern crate poisson;
+use poisson::process::PoissonProcess;
+
+extern crate rand;
+use rand::SeedableRng;
+
+extern crate rand_xorshift;
+use rand_xorshift::XorShiftRng;
+
+extern crate nalgebra as na;
+pub type Vect = na::Vector2<f64>;
+pub type Vect3 = na::Vector3<f64>;
+
+#[test]
+fn homogeneous_has_expected_amount_of_samples() {
+    let mut rand = XorShiftRng::from_seed([1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16]);
+    let process = PoissonProcess::<_, Vect3, _>::homogeneous(100.);
+    let runs = 200;
+    let mut total = 0;
+    for _ in 0..runs {
+        let samples = process.generate(&mut rand);
+        assert!(samples.iter().all(|s| s.iter().all(|&c| 0. <= c && c < 1.)));
+        total += samples.len();
+    }
+    let mean = total as f64 / runs as f64;
+    assert!((mean - 100.).abs() < 3., "Mean amount of samples {} was too far from 100.", mean);
+}
+
+#[test]
+fn inhomogeneous_follows_intensity() {
+    let mut rand = XorShiftRng::from_seed([1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16]);
+    let process = PoissonProcess::inhomogeneous(|v: &Vect| 200. * v.x, 200.);
+    let runs = 200;
+    let (mut left, mut right) = (0, 0);
+    for _ in 0..runs {
+        for s in process.iter(&mut rand) {
+            if s.x < 0.5 {
+                left += 1;
+            } else {
+                right += 1;
+            }
+        }
+    }
+    let mean = (left + right) as f64 / runs as f64;
+    assert!((mean - 100.).abs() < 3., "Mean amount of samples {} was too far from 100.", mean);
+    // Integral of the intensity over the right half is three times the one over the left half.
+    let ratio = right as f64 / left as f64;
+    assert!((ratio - 3.).abs() < 0.3, "Ratio of samples between halves {} was too far from 3.", ratio);
+}
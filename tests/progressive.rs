@@ -0,0 +1,89 @@
+extern crate poisson;
+use poisson::{Type, Builder, algorithm};
+use poisson::progressive::order;
+
+extern crate rand;
+use rand::{Rng, SeedableRng};
+
+extern crate rand_xorshift;
+use rand_xorshift::XorShiftRng;
+
+extern crate nalgebra as na;
+pub type Vect = na::Vector2<f64>;
+
+fn rng() -> XorShiftRng {
+    XorShiftRng::from_seed([1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16])
+}
+
+fn dist(v1: Vect, v2: Vect, poisson_type: Type) -> f64 {
+    let mut d = (v1 - v2).abs();
+    if poisson_type == Type::Perioditic {
+        d = d.map(|c| c.min(1. - c));
+    }
+    d.norm()
+}
+
+fn naive_order(samples: &[Vect], poisson_type: Type) -> Vec<Vect> {
+    let mut result = vec![samples[0]];
+    let mut dists = samples.iter().map(|&s| dist(samples[0], s, poisson_type)).collect::<Vec<_>>();
+    while result.len() < samples.len() {
+        let (i, _) = dists.iter()
+            .enumerate()
+            .fold((0, -1.), |(bi, bd), (i, &d)| if d > bd { (i, d) } else { (bi, bd) });
+        result.push(samples[i]);
+        for (j, d) in dists.iter_mut().enumerate() {
+            *d = d.min(dist(samples[i], samples[j], poisson_type));
+        }
+    }
+    result
+}
+
+#[test]
+fn order_matches_naive_farthest_point_order() {
+    for &poisson_type in &[Type::Normal, Type::Perioditic] {
+        let samples = Builder::<_, Vect>::with_radius(0.03, poisson_type)
+            .build(rng(), algorithm::Ebeida)
+            .generate();
+        let progressive = order(&samples, poisson_type);
+        assert_eq!(naive_order(&samples, poisson_type), progressive.samples());
+    }
+}
+
+#[test]
+fn radii_are_half_of_smallest_distance_of_prefixes() {
+    let samples = Builder::<_, Vect>::with_radius(0.02, Type::Normal)
+        .build(rng(), algorithm::Ebeida)
+        .generate();
+    let progressive = order(&samples, Type::Normal);
+    assert_eq!(samples.len(), progressive.samples().len());
+    assert!(progressive.radius(1).is_infinite());
+    for &length in &[2, 10, 50, 200, samples.len()] {
+        let prefix = &progressive.samples()[..length];
+        let mut min = std::f64::INFINITY;
+        for (i, &a) in prefix.iter().enumerate() {
+            for &b in &prefix[i + 1..] {
+                min = min.min((a - b).norm());
+            }
+        }
+        assert_eq!(min / 2., progressive.radius(length));
+    }
+    assert!(progressive.radius(samples.len()) >= 0.02);
+    assert_eq!(samples.len(), progressive.prefix(0.02).len());
+    let prefix = progressive.prefix(0.1);
+    assert!(prefix.len() > 10);
+    assert!(progressive.radius(prefix.len()) >= 0.1);
+    assert!(progressive.radius(prefix.len() + 1) < 0.1);
+}
+
+#[test]
+fn six_dimensional_order_keeps_every_sample() {
+    let mut rng = rng();
+    let samples = (0..2000)
+        .map(|_| na::Vector6::new(rng.gen(), rng.gen(), rng.gen(), rng.gen(), rng.gen(), rng.gen()))
+        .collect::<Vec<na::Vector6<f64>>>();
+    for &poisson_type in &[Type::Normal, Type::Perioditic] {
+        let progressive = order(&samples, poisson_type);
+        assert_eq!(samples.len(), progressive.samples().len());
+        assert!(progressive.radius(2) < progressive.radius(1));
+    }
+}
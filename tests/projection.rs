@@ -0,0 +1,72 @@
+extern crate poisson;
+use poisson::{Type, Builder};
+use poisson::projection::{Latinized, projected_distance};
+
+extern crate rand;
+use rand::SeedableRng;
+
+extern crate rand_xorshift;
+use rand_xorshift::XorShiftRng;
+
+extern crate nalgebra as na;
+pub type Vect = na::VectorN<f64, na::U10>;
+
+fn rng() -> XorShiftRng {
+    XorShiftRng::from_seed([1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16])
+}
+
+fn check(samples: &[Vect], radius: f64, axis_spacing: f64, pair_spacing: Option<f64>, poisson_type: Type) {
+    let all = (0..10).collect::<Vec<_>>();
+    assert!(projected_distance(samples, &all, poisson_type) >= 2. * radius);
+    for a in 0..10 {
+        assert!(projected_distance(samples, &[a], poisson_type) >= axis_spacing - 1e-12);
+        if let Some(pair_spacing) = pair_spacing {
+            for b in a + 1..10 {
+                assert!(projected_distance(samples, &[a, b], poisson_type) >= pair_spacing - 1e-12);
+            }
+        }
+    }
+}
+
+#[test]
+fn projections_to_axes_are_spaced_in_high_dimension() {
+    for &poisson_type in &[Type::Normal, Type::Perioditic] {
+        let (radius, axis_spacing) = (0.3, 0.02);
+        let latinized = Latinized::new(Builder::<_, Vect>::with_radius(radius, poisson_type), axis_spacing);
+        let samples = latinized.generate(&mut rng());
+        assert!(samples.len() > 20, "{}", samples.len());
+        check(&samples, radius, axis_spacing, None, poisson_type);
+    }
+}
+
+#[test]
+fn projections_to_pairs_of_axes_are_spaced() {
+    let (radius, axis_spacing, pair_spacing) = (0.3, 0.02, 0.08);
+    let mut latinized = Latinized::new(Builder::<_, Vect>::with_radius(radius, Type::Normal), axis_spacing);
+    latinized.set_pair_spacing(Some(pair_spacing));
+    let samples = latinized.generate(&mut rng());
+    assert!(samples.len() > 10, "{}", samples.len());
+    check(&samples, radius, axis_spacing, Some(pair_spacing), Type::Normal);
+}
+
+#[test]
+fn generation_stops_when_axes_are_full() {
+    let latinized = Latinized::new(Builder::<_, na::Vector2<f64>>::with_radius(0.001, Type::Perioditic), 0.1);
+    let samples = latinized.generate(&mut rng());
+    assert!(samples.len() <= 10);
+    assert!(projected_distance(&samples, &[0], Type::Perioditic) >= 0.1 - 1e-12);
+}
+
+#[test]
+fn projections_are_spaced_in_low_dimension() {
+    for &poisson_type in &[Type::Normal, Type::Perioditic] {
+        let (radius, axis_spacing) = (0.05, 0.01);
+        let latinized = Latinized::new(Builder::<_, na::Vector3<f64>>::with_radius(radius, poisson_type), axis_spacing);
+        let samples = latinized.generate(&mut rng());
+        assert!(samples.len() > 50, "{}", samples.len());
+        assert!(projected_distance(&samples, &[0, 1, 2], poisson_type) >= 2. * radius);
+        for a in 0..3 {
+            assert!(projected_distance(&samples, &[a], poisson_type) >= axis_spacing - 1e-12);
+        }
+    }
+}
@@ -0,0 +1,71 @@
+extern crate poisson;
+use poisson::{Type, Builder, algorithm};
+
+extern crate rand;
+use rand::SeedableRng;
+
+extern crate rand_xorshift;
+use rand_xorshift::XorShiftRng;
+
+extern crate nalgebra as na;
+pub type Vect = na::Vector2<f64>;
+
+mod helper;
+
+fn rng() -> XorShiftRng {
+    XorShiftRng::from_seed([1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16])
+}
+
+fn refill<A>(algo: A)
+    where A: algorithm::Creator<f64, Vect>,
+{
+    let radius = 0.02;
+    let mut iter = Builder::<_, Vect>::with_radius(radius, Type::Normal)
+        .build(rng(), algo)
+        .into_iter();
+    let mut samples = iter.by_ref().collect::<Vec<_>>();
+    let center = Vect::new(0.5, 0.5);
+    let region = |v: &Vect| (v - center).norm() < 0.2;
+    let inside = samples.iter().filter(|v| region(v)).count();
+    let removed = iter.refill_region(region);
+    assert_eq!(inside, removed.len());
+    samples.retain(|v| !region(v));
+    let kept = samples.len();
+    samples.extend(iter.by_ref());
+    assert!(samples.len() - kept > inside / 2,
+            "Only {} of {} removed samples were refilled using the '{:?}' algorithm.",
+            samples.len() - kept,
+            inside,
+            algo);
+    for s in &samples[kept..] {
+        assert!((s - center).norm() < 0.2 + 8. * radius);
+    }
+    helper::assert_legal_poisson(&samples, radius, algo);
+}
+
+#[test]
+fn refilling_region_with_ebeida_regenerates_only_there() {
+    refill(algorithm::Ebeida);
+}
+
+#[test]
+fn refilling_region_with_bridson_regenerates_only_there() {
+    refill(algorithm::Bridson);
+}
+
+#[test]
+fn removed_sample_frees_its_space() {
+    let radius = 0.05;
+    let mut iter = Builder::<_, Vect>::with_radius(radius, Type::Perioditic)
+        .build(rng(), algorithm::Ebeida)
+        .into_iter();
+    let samples = iter.by_ref().collect::<Vec<_>>();
+    let sample = samples[samples.len() / 2];
+    assert!(!iter.stays_legal(sample));
+    assert!(iter.remove(sample));
+    assert!(!iter.remove(sample));
+    assert!(iter.stays_legal(sample));
+    for s in iter {
+        assert!((s - sample).norm() < 6. * radius || (s - sample).norm() > 0.5);
+    }
+}
@@ -0,0 +1,84 @@
+extern crate poisson;
+use poisson::{Type, Builder, algorithm};
+use poisson::spatial::SampleIndex;
+
+extern crate rand;
+use rand::{Rng, SeedableRng};
+
+extern crate rand_xorshift;
+use rand_xorshift::XorShiftRng;
+
+extern crate nalgebra as na;
+pub type Vect = na::Vector2<f64>;
+
+fn rng() -> XorShiftRng {
+    XorShiftRng::from_seed([1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16])
+}
+
+fn dist(v1: Vect, v2: Vect, poisson_type: Type) -> f64 {
+    let mut d = (v1 - v2).abs();
+    if poisson_type == Type::Perioditic {
+        d = d.map(|c| c.min(1. - c));
+    }
+    d.norm()
+}
+
+#[test]
+fn queries_match_brute_force() {
+    let mut rand = rng();
+    for &poisson_type in &[Type::Normal, Type::Perioditic] {
+        let generator = Builder::<_, Vect>::with_radius(0.03, poisson_type).build(rng(), algorithm::Ebeida);
+        let index = SampleIndex::from_generator(generator);
+        let samples = index.samples().to_vec();
+        assert!(!samples.is_empty());
+        for _ in 0..50 {
+            let point = Vect::new(rand.gen(), rand.gen());
+            let distance = rand.gen::<f64>() * 0.3;
+
+            let mut within = index.within(&point, distance);
+            within.sort();
+            let expected = (0..samples.len())
+                .filter(|&i| dist(samples[i], point, poisson_type) <= distance)
+                .collect::<Vec<_>>();
+            assert_eq!(expected, within);
+
+            let nearest = index.nearest(&point, 5);
+            let mut expected = (0..samples.len())
+                .map(|i| dist(samples[i], point, poisson_type))
+                .collect::<Vec<_>>();
+            expected.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            assert_eq!(5, nearest.len());
+            for (&(i, d), e) in nearest.iter().zip(expected) {
+                assert!((d - e).abs() < 1e-12);
+                assert!((dist(samples[i], point, poisson_type) - d).abs() < 1e-12);
+            }
+        }
+
+        let mut expected = vec![];
+        for i in 0..samples.len() {
+            for j in i + 1..samples.len() {
+                if dist(samples[i], samples[j], poisson_type) <= 0.1 {
+                    expected.push((i, j));
+                }
+            }
+        }
+        assert_eq!(expected, index.pairs(0.1));
+    }
+}
+
+#[test]
+fn nearest_returns_every_sample_when_asked_for_more() {
+    let samples = vec![Vect::new(0.1, 0.1), Vect::new(0.9, 0.9), Vect::new(0.5, 0.2)];
+    let index = SampleIndex::new(samples, 0.01, Type::Normal);
+    let nearest = index.nearest(&Vect::new(0., 0.), 10);
+    assert_eq!(vec![0, 2, 1], nearest.iter().map(|&(i, _)| i).collect::<Vec<_>>());
+}
+
+#[test]
+fn perioditic_pairs_wrap_around() {
+    let samples = vec![Vect::new(0.01, 0.5), Vect::new(0.99, 0.5), Vect::new(0.5, 0.5)];
+    let normal = SampleIndex::new(samples.clone(), 0.05, Type::Normal);
+    let perioditic = SampleIndex::new(samples, 0.05, Type::Perioditic);
+    assert!(normal.pairs(0.1).is_empty());
+    assert_eq!(vec![(0, 1)], perioditic.pairs(0.1));
+}
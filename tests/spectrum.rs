@@ -0,0 +1,59 @@
+extern crate poisson;
+use poisson::{Type, Builder, algorithm};
+use poisson::baseline::WhiteNoise;
+use poisson::spectrum::{Synthesis, Target, step_spectrum, radial_spectrum, pair_correlation};
+
+extern crate rand;
+use rand::SeedableRng;
+
+extern crate rand_xorshift;
+use rand_xorshift::XorShiftRng;
+
+extern crate nalgebra as na;
+pub type Vect = na::Vector2<f64>;
+
+fn rng() -> XorShiftRng {
+    XorShiftRng::from_seed([1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16])
+}
+
+#[test]
+fn white_noise_has_flat_spectrum() {
+    let samples: Vec<Vect> = WhiteNoise::new(1000).generate(&mut rng());
+    let spectrum = radial_spectrum(&samples, 32);
+    let mean = spectrum[8..].iter().sum::<f64>() / spectrum[8..].len() as f64;
+    assert!((mean - 1.).abs() < 0.1, "Mean power {} of white noise was too far from 1.", mean);
+}
+
+#[test]
+fn white_noise_has_flat_pair_correlation() {
+    let samples: Vec<Vect> = WhiteNoise::new(1000).generate(&mut rng());
+    let pcf = pair_correlation(&samples, 10, 0.25);
+    let mean = pcf.iter().sum::<f64>() / pcf.len() as f64;
+    assert!((mean - 1.).abs() < 0.1, "Mean pair correlation {} of white noise was too far from 1.", mean);
+}
+
+#[test]
+fn synthesis_approaches_target_spectrum() {
+    let samples = Builder::<_, Vect>::with_samples(64, 0.5, Type::Perioditic)
+        .build(rng(), algorithm::Ebeida)
+        .generate();
+    let mut synthesis = Synthesis::new(Target::Spectrum(step_spectrum(12, 6)));
+    synthesis.set_iterations(50);
+    let (amount, before) = (samples.len(), synthesis.loss(&samples));
+    let optimized = synthesis.optimize(samples);
+    assert_eq!(amount, optimized.len());
+    assert!(optimized.iter().all(|s| s.iter().all(|&c| 0. <= c && c < 1.)));
+    assert!(synthesis.loss(&optimized) < before / 2.);
+}
+
+#[test]
+fn synthesis_approaches_target_pair_correlation() {
+    let samples: Vec<Vect> = WhiteNoise::new(64).generate(&mut rng());
+    let target = (1..11).map(|b| if b < 4 { 0. } else { 1. }).collect();
+    let mut synthesis = Synthesis::new(Target::PairCorrelation(target, 0.25));
+    synthesis.set_iterations(50);
+    let before = synthesis.loss(&samples);
+    let optimized = synthesis.optimize(samples);
+    assert!(optimized.iter().all(|s| s.iter().all(|&c| 0. <= c && c < 1.)));
+    assert!(synthesis.loss(&optimized) < before / 2.);
+}
@@ -0,0 +1,94 @@
+extern crate poisson;
+use poisson::{Type, Builder};
+use poisson::symmetry::{Symmetric, Symmetry, Wallpaper};
+
+extern crate rand;
+use rand::SeedableRng;
+
+extern crate rand_xorshift;
+use rand_xorshift::XorShiftRng;
+
+extern crate nalgebra as na;
+pub type Vect = na::Vector2<f64>;
+pub type Vect3 = na::Vector3<f64>;
+
+fn rng() -> XorShiftRng {
+    XorShiftRng::from_seed([1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16])
+}
+
+fn toroidal(v: Vect) -> Vect {
+    v.map(|c| c - c.round())
+}
+
+fn assert_symmetric<T: Fn(Vect) -> Vect>(samples: &[Vect], transform: T) {
+    for &s in samples {
+        let image = transform(s);
+        assert!(samples.iter().any(|&o| toroidal(o - image).norm() < 1e-9),
+                "Image {:?} of {:?} was missing from the distribution.", image, s);
+    }
+}
+
+fn assert_legal(samples: &[Vect], radius: f64, poisson_type: Type) {
+    for (i, &v1) in samples.iter().enumerate() {
+        for &v2 in &samples[i + 1..] {
+            let diff = if poisson_type == Type::Perioditic { toroidal(v1 - v2) } else { v1 - v2 };
+            assert!(diff.norm() >= 2. * radius, "Samples {:?} and {:?} are too close.", v1, v2);
+        }
+    }
+}
+
+#[test]
+fn mirrored_is_symmetric() {
+    let radius = 0.03;
+    let symmetric = Symmetric::new(Builder::<_, Vect>::with_radius(radius, Type::Normal), Symmetry::Mirror(vec![0, 1]));
+    let samples = symmetric.generate(&mut rng());
+    assert!(samples.len() > 50);
+    assert_legal(&samples, radius, Type::Normal);
+    assert_symmetric(&samples, |v| Vect::new(1. - v.x, v.y));
+    assert_symmetric(&samples, |v| Vect::new(v.x, 1. - v.y));
+}
+
+#[test]
+fn mirrored_in_3d_is_symmetric() {
+    let radius = 0.08;
+    let symmetric = Symmetric::new(Builder::<_, Vect3>::with_radius(radius, Type::Perioditic), Symmetry::Mirror(vec![2]));
+    let samples = symmetric.generate(&mut rng());
+    assert!(!samples.is_empty());
+    for s in &samples {
+        let image = Vect3::new(s.x, s.y, 1. - s.z);
+        assert!(samples.iter().any(|o| (o - image).norm() < 1e-9));
+    }
+}
+
+#[test]
+fn rotated_is_symmetric() {
+    let radius = 0.03;
+    let symmetric = Symmetric::new(Builder::<_, Vect>::with_radius(radius, Type::Normal), Symmetry::Rotation(5));
+    let samples = symmetric.generate(&mut rng());
+    assert!(samples.len() > 50);
+    assert!(samples.len() % 5 == 0);
+    assert_legal(&samples, radius, Type::Normal);
+    let (sin, cos) = (2. * std::f64::consts::PI / 5.).sin_cos();
+    assert_symmetric(&samples, |v| {
+        let (x, y) = (v.x - 0.5, v.y - 0.5);
+        Vect::new(0.5 + x * cos - y * sin, 0.5 + x * sin + y * cos)
+    });
+}
+
+#[test]
+fn wallpaper_is_symmetric() {
+    let radius = 0.03;
+    let symmetric = Symmetric::new(Builder::<_, Vect>::with_radius(radius, Type::Perioditic), Symmetry::Wallpaper(Wallpaper::P4m));
+    let samples = symmetric.generate(&mut rng());
+    assert!(samples.len() > 50);
+    assert_legal(&samples, radius, Type::Perioditic);
+    assert_symmetric(&samples, |v| Vect::new(-v.y, v.x));
+    assert_symmetric(&samples, |v| Vect::new(v.y, v.x));
+    assert_symmetric(&samples, |v| Vect::new(-v.x, v.y));
+}
+
+#[test]
+#[should_panic]
+fn wallpaper_requires_perioditic() {
+    let _ = Symmetric::new(Builder::<_, Vect>::with_radius(0.03, Type::Normal), Symmetry::Wallpaper(Wallpaper::P2));
+}
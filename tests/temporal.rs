@@ -0,0 +1,139 @@
+extern crate poisson;
+use poisson::{Type, Builder, algorithm};
+use poisson::obstacle::Obstacle;
+use poisson::temporal::Frame;
+
+extern crate rand;
+use rand::SeedableRng;
+
+extern crate rand_xorshift;
+use rand_xorshift::XorShiftRng;
+
+extern crate nalgebra as na;
+pub type Vect = na::Vector2<f64>;
+
+fn rng(seed: u8) -> XorShiftRng {
+    XorShiftRng::from_seed([seed, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16])
+}
+
+/// Checks that every point of a fine grid over the part of the space is covered by a disk of a sample.
+fn assert_covered<P>(frame: &Frame<Vect>, radius: f64, part: P)
+    where P: Fn(&Vect) -> bool
+{
+    let steps = 200;
+    for x in 0..steps + 1 {
+        for y in 0..steps + 1 {
+            let probe = Vect::new(x as f64, y as f64) / steps as f64;
+            if part(&probe) {
+                assert!(frame.samples().iter().any(|s| (s.value - probe).norm() < 2. * radius),
+                        "{:?} was left uncovered.", probe);
+            }
+        }
+    }
+}
+
+fn assert_legal(frame: &Frame<Vect>, radius: f64) {
+    let samples = frame.samples();
+    for (i, s1) in samples.iter().enumerate() {
+        for s2 in &samples[i + 1..] {
+            assert!(s1.id != s2.id);
+            assert!((s1.value - s2.value).norm() >= 2. * radius,
+                    "Samples {:?} and {:?} are too close.", s1, s2);
+        }
+    }
+}
+
+#[test]
+fn shrinking_radius_keeps_all_samples() {
+    let first = Frame::new().next(Builder::<_, Vect>::with_radius(0.05, Type::Normal).build(rng(1), algorithm::Ebeida));
+    assert_legal(&first, 0.05);
+    let second = first.next(Builder::<_, Vect>::with_radius(0.04, Type::Normal).build(rng(2), algorithm::Ebeida));
+    assert_legal(&second, 0.04);
+    assert!(second.samples().len() > first.samples().len());
+    for s in first.samples() {
+        assert_eq!(Some(s), second.get(s.id));
+    }
+}
+
+#[test]
+fn growing_radius_and_shrinking_domain_removes_samples() {
+    let first = Frame::new().next(Builder::<_, Vect>::with_radius(0.04, Type::Normal).build(rng(1), algorithm::Ebeida));
+    let domain = Obstacle::Box(Vect::new(0., 0.), Vect::new(0.5, 1.));
+    let second = first.next_within(Builder::<_, Vect>::with_radius(0.05, Type::Normal).build(rng(2), algorithm::Ebeida), domain);
+    assert_legal(&second, 0.05);
+    assert!(second.samples().iter().all(|s| s.value.x <= 0.5));
+    let kept = second.samples().iter().filter(|s| first.get(s.id).is_some()).count();
+    assert!(kept > 0);
+    assert!(kept < first.samples().len());
+    for s in second.samples() {
+        if let Some(old) = first.get(s.id) {
+            assert_eq!(old, s);
+        }
+    }
+}
+
+#[test]
+fn mapped_samples_keep_identity() {
+    let first = Frame::new().next(Builder::<_, Vect>::with_radius(0.05, Type::Normal).build(rng(1), algorithm::Ebeida));
+    let zoomed = first.clone().map(|v| v * 2.);
+    let second = zoomed.next(Builder::<_, Vect>::with_radius(0.05, Type::Normal).build(rng(2), algorithm::Ebeida));
+    assert_legal(&second, 0.05);
+    for s in second.samples() {
+        if let Some(old) = first.get(s.id) {
+            assert_eq!(old.value * 2., s.value);
+        }
+    }
+}
+
+fn translated<A>(algo: A) -> Frame<Vect>
+    where A: algorithm::Creator<f64, Vect> + Copy,
+{
+    let first = Frame::new().next(Builder::<_, Vect>::with_radius(0.03, Type::Normal).build(rng(1), algo));
+    let moved = first.map(|v| v + Vect::new(0.3, 0.));
+    moved.next(Builder::<_, Vect>::with_radius(0.03, Type::Normal).build(rng(2), algo))
+}
+
+#[test]
+fn translated_frame_drops_samples_outside_of_space() {
+    for frame in &[translated(algorithm::Ebeida), translated(algorithm::Bridson)] {
+        assert_legal(frame, 0.03);
+        assert!(frame.samples().iter().all(|s| s.value.iter().all(|&c| 0. <= c && c <= 1.)));
+    }
+}
+
+#[test]
+fn translated_frame_covers_whole_space() {
+    assert_covered(&translated(algorithm::Ebeida), 0.03, |_| true);
+}
+
+#[test]
+fn translated_frame_covers_domain_up_to_its_border() {
+    let domain = Obstacle::Polygon(vec![Vect::new(0.1, 0.1), Vect::new(0.9, 0.2), Vect::new(0.5, 0.9)]);
+    let first = Frame::new().next(Builder::<_, Vect>::with_radius(0.03, Type::Normal).build(rng(1), algorithm::Ebeida));
+    let moved = first.map(|v| v + Vect::new(0.2, 0.1));
+    let second = moved.next_within(Builder::<_, Vect>::with_radius(0.03, Type::Normal).build(rng(2), algorithm::Ebeida), domain.clone());
+    assert_legal(&second, 0.03);
+    assert!(second.samples().iter().all(|s| domain.distance(&s.value) == 0.));
+    assert_covered(&second, 0.03, |p| domain.distance(p) == 0.);
+}
+
+#[test]
+fn density_thins_samples_coherently() {
+    let radius = 0.01;
+    let generator = |seed| Builder::<_, Vect>::with_radius(radius, Type::Normal).build(rng(seed), algorithm::Ebeida);
+    let sparse = |v: &Vect| if v.x < 0.5 { 1. } else { 0.2 };
+    let first = Frame::new().next_with_density(generator(1), sparse);
+    let left = first.samples().iter().filter(|s| s.value.x < 0.5).count();
+    let right = first.samples().len() - left;
+    assert!(right * 10 > left && right * 3 < left, "{} {}", left, right);
+    assert_legal(&first, radius);
+    // Samples kept with lower density are kept with higher density too.
+    let denser = first.next_with_density(generator(2), |v: &Vect| if v.x < 0.5 { 1. } else { 0.6 });
+    for s in first.samples() {
+        assert_eq!(Some(s), denser.get(s.id));
+    }
+    let right = denser.samples().iter().filter(|s| s.value.x >= 0.5).count();
+    assert!(right * 2 > left, "{} {}", left, right);
+    assert_legal(&denser, radius);
+    assert_covered(&denser, radius, |p| p.x < 0.49);
+}
@@ -0,0 +1,69 @@
+extern crate poisson;
+use poisson::{Type, Builder, algorithm};
+use poisson::well_spaced::WellSpaced;
+
+extern crate rand;
+use rand::{Rng, SeedableRng};
+
+extern crate rand_xorshift;
+use rand_xorshift::XorShiftRng;
+
+extern crate nalgebra as na;
+pub type Vect = na::Vector2<f64>;
+
+fn rng() -> XorShiftRng {
+    XorShiftRng::from_seed([1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16])
+}
+
+fn assert_well_spaced(samples: &[Vect], radius: f64, max_gap: f64) {
+    for (i, &v1) in samples.iter().enumerate() {
+        for &v2 in &samples[i + 1..] {
+            assert!((v1 - v2).norm() >= 2. * radius, "Samples {:?} and {:?} are too close.", v1, v2);
+        }
+    }
+    let mut rand = rng();
+    for _ in 0..10000 {
+        let probe = rand.gen::<Vect>();
+        assert!(samples.iter().any(|&s| (s - probe).norm() <= max_gap),
+                "Point {:?} is farther than {} from all samples.", probe, max_gap);
+    }
+}
+
+#[test]
+fn well_spaced_fills_gaps_of_non_maximal() {
+    let radius = 0.04;
+    let max_gap = 3. * radius;
+    let generator = Builder::<_, Vect>::with_radius(radius, Type::Normal).build(rng(), algorithm::Bridson);
+    let (samples, coverage) = WellSpaced::new(max_gap).generate(generator);
+    assert!(coverage.met);
+    assert_well_spaced(&samples, radius, max_gap);
+    assert!(WellSpaced::new(max_gap).check(&samples, Type::Normal).met);
+}
+
+#[test]
+fn well_spaced_reaches_near_maximal_bound() {
+    let radius = 0.04;
+    let max_gap = 2.02 * radius;
+    let generator = Builder::<_, Vect>::with_radius(radius, Type::Normal).build(rng(), algorithm::Ebeida);
+    let (samples, coverage) = WellSpaced::new(max_gap).generate(generator);
+    assert!(coverage.met);
+    assert_well_spaced(&samples, radius, max_gap);
+}
+
+#[test]
+fn too_small_gap_is_not_met() {
+    let samples = vec![Vect::new(0.5, 0.5)];
+    let coverage = WellSpaced::new(0.5).check(&samples, Type::Normal);
+    assert!(!coverage.met);
+    assert!(coverage.uncovered > 0);
+    assert!(WellSpaced::new(0.75).check(&samples, Type::Normal).met);
+}
+
+#[test]
+fn samples_on_upper_edge_cover_the_space() {
+    let samples = vec![Vect::new(1., 1.)];
+    assert!(WellSpaced::new(1.5).check(&samples, Type::Normal).met);
+    assert!(!WellSpaced::new(1.3).check(&samples, Type::Normal).met);
+    let samples = vec![Vect::new(1., 0.5), Vect::new(-0.2, 0.5)];
+    assert!(WellSpaced::new(0.8).check(&samples, Type::Normal).met);
+}